@@ -3,6 +3,495 @@
 
 /* auto-generated by NAPI-RS */
 
+/** An error collected while parsing a single face, instead of being thrown. */
+export interface FaceError {
+  /** Same value as the `code` property of a thrown error. */
+  code: string
+  /** A human-readable description of the error. */
+  message: string
+}
+/** A [character encoding subtable](https://docs.microsoft.com/en-us/typography/opentype/spec/cmap#encoding-records-and-encodings). */
+export interface Subtable {
+  /** Subtable platform. */
+  platformId: PlatformId
+  /** Subtable encoding. */
+  encodingId: number
+  /** Subtable format, e.g. `4` or `12`. */
+  format: number
+  /** Checks that the current encoding is Unicode compatible. */
+  isUnicode: boolean
+}
+/** A [Character to Glyph Index Mapping Table](https://docs.microsoft.com/en-us/typography/opentype/spec/cmap). */
+export interface CMAPTable {
+  /** A list of subtables. */
+  subtables: Array<Subtable>
+}
+/** A [variation axis](https://docs.microsoft.com/en-us/typography/opentype/spec/fvar#variationaxisrecord). */
+export interface VariationAxis {
+  /** Axis tag, e.g. `wght`. */
+  tag: string
+  /** Minimum coordinate value. */
+  minValue: number
+  /** Default coordinate value. */
+  defaultValue: number
+  /** Maximum coordinate value. */
+  maxValue: number
+  /** Checks that the axis should not be exposed in user interfaces. */
+  hidden: boolean
+  /** Axis name ID in the `name` table. */
+  nameId: number
+  /** Axis name, `undefined` when not present in the `name` table. */
+  name?: string
+}
+/** A [named instance](https://docs.microsoft.com/en-us/typography/opentype/spec/fvar#instancerecord). */
+export interface NamedInstance {
+  /** Coordinates of the instance, in the order of the axes. */
+  coordinates: Array<number>
+  /** Subfamily name ID in the `name` table. */
+  subfamilyNameId: number
+  /** Subfamily name, e.g. `Bold`, `undefined` when not present in the `name` table. */
+  subfamilyName?: string
+  /** PostScript name ID in the `name` table, `undefined` when the instance has none. */
+  postScriptNameId?: number
+  /**
+   * PostScript name, `undefined` when the instance has none or when it is
+   * not present in the `name` table.
+   */
+  postScriptName?: string
+}
+/** A [Font Variations Table](https://docs.microsoft.com/en-us/typography/opentype/spec/fvar). */
+export interface FVARTable {
+  /** A list of variation axes. */
+  axes: Array<VariationAxis>
+  /** A list of named instances. */
+  instances: Array<NamedInstance>
+}
+/** A [design axis](https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-records). */
+export interface DesignAxis {
+  /** Axis tag, e.g. `wght`. */
+  tag: string
+  /** Axis name ID in the `name` table. */
+  nameId: number
+  /** Axis name, `undefined` when not present in the `name` table. */
+  name?: string
+  /** Position of the axis when composing style names, lowest first. */
+  ordering: number
+}
+/** A value of a single axis in a format 4 axis value. */
+export interface AxisValueRecord {
+  /** Index of the axis in the design axes. */
+  axisIndex: number
+  /** Axis value. */
+  value: number
+}
+/**
+ * An [axis value](https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-value-tables)
+ * of any format.
+ *
+ * Fields not used by a format are `undefined`.
+ */
+export interface AxisValue {
+  /** Axis value table format, from 1 to 4. */
+  format: number
+  /** Index of the axis in the design axes. Not used by format 4. */
+  axisIndex?: number
+  /** Raw axis value flags. */
+  flags: number
+  /** Checks that the value applies to older fonts of the family as well. */
+  olderSiblingFontAttribute: boolean
+  /**
+   * Checks that the value name can be omitted when composing a style name,
+   * e.g. `Regular`.
+   */
+  elidable: boolean
+  /** Value name ID in the `name` table. */
+  valueNameId: number
+  /** Value name, e.g. `Semibold`, `undefined` when not present in the `name` table. */
+  name?: string
+  /** Axis value of formats 1 and 3. */
+  value?: number
+  /** Nominal value of format 2. */
+  nominalValue?: number
+  /** Minimum value of the range of format 2. */
+  rangeMinValue?: number
+  /** Maximum value of the range of format 2. */
+  rangeMaxValue?: number
+  /** Style-linked value of format 3, e.g. `700` for `400`. */
+  linkedValue?: number
+  /** Values of every axis of format 4. */
+  axisValues?: Array<AxisValueRecord>
+}
+/** A [Style Attributes Table](https://docs.microsoft.com/en-us/typography/opentype/spec/stat). */
+export interface STATTable {
+  /** Design axes, in the order referenced by axis indices. */
+  designAxes: Array<DesignAxis>
+  /** Axis values. Values of an unknown format are skipped. */
+  axisValues: Array<AxisValue>
+  /**
+   * Name ID of the name used when every value is elided, e.g. `Regular`.
+   *
+   * `undefined` for version 1.0 tables.
+   */
+  elidedFallbackNameId?: number
+  /**
+   * Name used when every value is elided, `undefined` when not present
+   * in the `name` table.
+   */
+  elidedFallbackName?: string
+}
+/** A [glyph class](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#glyph-class-definition-table). */
+export const enum GlyphClass {
+  /** A glyph missing from the class definition. */
+  Unclassified = 0,
+  /** A single character, spacing glyph. */
+  Base = 1,
+  /** A multiple character, spacing glyph. */
+  Ligature = 2,
+  /** A non-spacing combining glyph. */
+  Mark = 3,
+  /** A part of a single character, spacing glyph. */
+  Component = 4
+}
+/**
+ * A [caret value](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#caret-value-tables)
+ * of any format.
+ */
+export interface CaretValue {
+  /** Caret value table format, from 1 to 3. */
+  format: number
+  /** X or Y coordinate of the caret in font units, for formats 1 and 3. */
+  coordinate?: number
+  /** Index of the outline point the caret goes through, for format 2. */
+  pointIndex?: number
+}
+/** The caret positions inside a ligature glyph. */
+export interface LigatureCarets {
+  /** The ligature glyph id. */
+  glyphId: number
+  /**
+   * One caret between every pair of components, in increasing coordinate
+   * order.
+   */
+  carets: Array<CaretValue>
+}
+/** A [Glyph Definition Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef). */
+export interface GDEFTable {
+  /** Table version, e.g. `1.2`. */
+  version: number
+  /**
+   * Mark glyph sets, each one a list of glyph ids in increasing order.
+   *
+   * `undefined` for tables older than version 1.2.
+   */
+  markGlyphSets?: Array<Array<number>>
+  /** Caret positions of ligature glyphs, in increasing glyph id order. */
+  ligatureCarets: Array<LigatureCarets>
+  /**
+   * Checks that the table has an item variation store, used by variable
+   * fonts to adjust `GPOS` values and caret positions.
+   */
+  hasVariationStore: boolean
+}
+/** A [language system](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#language-system-table). */
+export interface LanguageSystem {
+  /** Language system tag, e.g. `TRK `. */
+  tag: string
+  /** Index of a feature required by the language system. */
+  requiredFeature?: number
+  /** Indices of the features of the language system. */
+  featureIndices: Array<number>
+}
+/** A [script](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#script-table). */
+export interface Script {
+  /** Script tag, e.g. `latn`. */
+  tag: string
+  /** Default language system of the script. */
+  defaultLanguage?: LanguageSystem
+  /** Other language systems of the script. */
+  languages: Array<LanguageSystem>
+}
+/** [Stylistic set parameters](https://docs.microsoft.com/en-us/typography/opentype/spec/features_pt#ss01---ss20). */
+export interface StylisticSetParams {
+  /** UI name ID in the `name` table. */
+  uiNameId: number
+  /** UI name, e.g. `Alternate a`, `undefined` when not present in the `name` table. */
+  uiName?: string
+}
+/** [Character variant parameters](https://docs.microsoft.com/en-us/typography/opentype/spec/features_ae#cv01-cv99). */
+export interface CharacterVariantParams {
+  /** UI label name ID in the `name` table, `0` when not set. */
+  uiLabelNameId: number
+  /** UI label, `undefined` when not set or not present in the `name` table. */
+  uiLabel?: string
+  /** Tooltip name ID in the `name` table, `0` when not set. */
+  tooltipNameId: number
+  /** Tooltip, `undefined` when not set or not present in the `name` table. */
+  tooltip?: string
+  /** Sample text name ID in the `name` table, `0` when not set. */
+  sampleTextNameId: number
+  /** Sample text, `undefined` when not set or not present in the `name` table. */
+  sampleText?: string
+  /** Labels of the named parameters, in order. */
+  paramUiLabels: Array<string>
+  /** Unicode codepoints the feature applies to. */
+  characters: Array<number>
+}
+/** [Optical size parameters](https://docs.microsoft.com/en-us/typography/opentype/spec/features_pt#size). */
+export interface SizeParams {
+  /** Design size in points. */
+  designSize: number
+  /** Identifies the fonts of a family sharing the same size range. */
+  subfamilyIdentifier: number
+  /** Subfamily name ID in the `name` table, `0` when not set. */
+  subfamilyNameId: number
+  /** Subfamily name, `undefined` when not set or not present in the `name` table. */
+  subfamilyName?: string
+  /** Smallest size of the intended range in points, exclusive. */
+  rangeStart: number
+  /** Largest size of the intended range in points, inclusive. */
+  rangeEnd: number
+}
+/** A [feature](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#feature-table). */
+export interface Feature {
+  /** Feature tag, e.g. `liga`. */
+  tag: string
+  /** Indices of the lookups of the feature. */
+  lookupIndices: Array<number>
+  /** Parameters of a stylistic set feature (`ss01` to `ss20`). */
+  stylisticSet?: StylisticSetParams
+  /** Parameters of a character variant feature (`cv01` to `cv99`). */
+  characterVariant?: CharacterVariantParams
+  /** Parameters of the `size` feature. */
+  size?: SizeParams
+}
+/**
+ * A [Glyph Substitution Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub)
+ * or a [Glyph Positioning Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos).
+ */
+export interface LayoutTable {
+  /** A list of scripts. */
+  scripts: Array<Script>
+  /** A list of features, referenced by index from language systems. */
+  features: Array<Feature>
+  /** Number of lookups. */
+  lookupCount: number
+}
+/** A [PostScript Table](https://docs.microsoft.com/en-us/typography/opentype/spec/post). */
+export interface POSTTable {
+  /** Table version, e.g. `2` or `2.5`. */
+  version: number
+  /** Italic angle in counter-clockwise degrees from the vertical. */
+  italicAngle: number
+  /** Underline metrics. */
+  underlineMetrics: LineMetrics
+  /** Checks that the face is monospaced. */
+  isFixedPitch: boolean
+  /** Minimum memory usage when the face is downloaded as a Type 42 font. */
+  minMemType42: number
+  /** Maximum memory usage when the face is downloaded as a Type 42 font. */
+  maxMemType42: number
+  /** Minimum memory usage when the face is downloaded as a Type 1 font. */
+  minMemType1: number
+  /** Maximum memory usage when the face is downloaded as a Type 1 font. */
+  maxMemType1: number
+}
+/** Metrics of a single glyph, in font units. */
+export interface GlyphMetrics {
+  /** Horizontal advance from the `hmtx` table. */
+  horAdvance?: number
+  /** Left side bearing from the `hmtx` table. */
+  leftSideBearing?: number
+  /** Vertical advance from the `vmtx` table. */
+  verAdvance?: number
+  /** Top side bearing from the `vmtx` table. */
+  topSideBearing?: number
+  /** Vertical origin from the `VORG` table. */
+  verticalOrigin?: number
+  /** Bounding box of the glyph, `undefined` for glyphs without an outline. */
+  bbox?: Rect
+}
+/**
+ * Metrics of all glyphs of a face, indexed by glyph id.
+ *
+ * Values missing for a glyph are stored as `0`.
+ */
+export interface GlyphMetricsArrays {
+  /** Horizontal advances from the `hmtx` table. */
+  horAdvances: Uint16Array
+  /** Left side bearings from the `hmtx` table. */
+  leftSideBearings: Int16Array
+  /** Vertical advances, `undefined` when the `vmtx` table is not present. */
+  verAdvances?: Uint16Array
+  /** Top side bearings, `undefined` when the `vmtx` table is not present. */
+  topSideBearings?: Int16Array
+  /** Vertical origins, `undefined` when the `VORG` table is not present. */
+  verticalOrigins?: Int16Array
+  /** Bounding boxes as `xMin, yMin, xMax, yMax` quadruples. */
+  bboxes: Int16Array
+}
+/**
+ * Every kerning pair of a face, sorted by left glyph id, then right glyph id.
+ *
+ * The arrays are parallel: the pair at index `i` is made of
+ * `leftGlyphs[i]` and `rightGlyphs[i]`, adjusted by `values[i]`.
+ */
+export interface KerningPairs {
+  /** Glyph ids of the left glyphs. */
+  leftGlyphs: Uint16Array
+  /** Glyph ids of the right glyphs. */
+  rightGlyphs: Uint16Array
+  /** Adjustments of the horizontal advance of the left glyphs, in font units. */
+  values: Int16Array
+}
+/** Options of `measureText`. */
+export interface MeasureTextOptions {
+  /** Applies pair kerning from the legacy `kern` table. Defaults to `true`. */
+  kerning?: boolean
+}
+/** A bounding box in scaled units, y pointing up from the baseline. */
+export interface TextBounds {
+  xMin: number
+  yMin: number
+  xMax: number
+  yMax: number
+}
+/** The result of `measureText`, scaled to the requested font size. */
+export interface TextMeasurement {
+  /** The total advance width of the text. */
+  width: number
+  /** The union of all glyph bounding boxes, `undefined` when nothing is inked. */
+  inkBounds?: TextBounds
+  /** The advance of every character, kerning with the next one included. */
+  advances: Array<number>
+  /** The x position of every character's origin. */
+  positions: Array<number>
+}
+/** Options of `shape`. */
+export interface ShapeOptions {
+  /**
+   * ISO 15924 script tag, e.g. `Latn` or `Arab`. Guessed from the text when
+   * not set.
+   */
+  script?: string
+  /**
+   * BCP 47 language tag, e.g. `en` or `tr`, used to select a language
+   * system. The default language system is used when not set.
+   */
+  language?: string
+  /**
+   * Text direction, one of `ltr`, `rtl`, `ttb` or `btt`. Guessed from the
+   * script when not set.
+   */
+  direction?: string
+  /**
+   * Features to enable or disable in HarfBuzz syntax, e.g.
+   * `['-liga', 'ss01', 'kern=0']`. The default features of the script are
+   * applied as well.
+   */
+  features?: Array<string>
+}
+/** A positioned glyph, in font units. */
+export interface ShapedGlyph {
+  /** The glyph id. */
+  glyphId: number
+  /**
+   * Index of the first UTF-16 code unit of the characters the glyph was
+   * shaped from.
+   */
+  cluster: number
+  /** How much the line advances horizontally after drawing this glyph. */
+  xAdvance: number
+  /** How much the line advances vertically after drawing this glyph. */
+  yAdvance: number
+  /** How much the glyph moves horizontally before drawing it. */
+  xOffset: number
+  /** How much the glyph moves vertically before drawing it. */
+  yOffset: number
+}
+/** An alternate glyph of a character. */
+export interface GlyphAlternate {
+  /** The alternate glyph id. */
+  glyphId: number
+  /** Tag of the feature producing the alternate, e.g. `salt` or `ss01`. */
+  featureTag: string
+  /** Feature value selecting the alternate, e.g. `2` for `salt=2`. */
+  featureValue: number
+  /**
+   * UI name of a stylistic set or character variant feature, `undefined`
+   * when the font doesn't name the feature.
+   */
+  featureName?: string
+}
+/** A ligature substitution. */
+export interface LigatureSubstitution {
+  /** Tag of the feature applying the substitution, e.g. `liga`. */
+  featureTag: string
+  /** Glyph ids of the components, in logical order. */
+  components: Array<number>
+  /** Glyph id of the ligature. */
+  glyphId: number
+  /**
+   * The characters the components are mapped from in the `cmap` table,
+   * e.g. `=>`, `undefined` when a component isn't mapped from any character.
+   */
+  text?: string
+}
+/** A kind of path command. */
+export const enum PathCommandKind {
+  /** Starts a new contour at `x`, `y`. */
+  MoveTo = 0,
+  /** Draws a line to `x`, `y`. */
+  LineTo = 1,
+  /** Draws a quadratic Bézier curve through `x1`, `y1` to `x`, `y`. */
+  QuadTo = 2,
+  /** Draws a cubic Bézier curve through `x1`, `y1` and `x2`, `y2` to `x`, `y`. */
+  CurveTo = 3,
+  /** Closes the current contour. */
+  Close = 4
+}
+/** A single path command of a glyph outline, in font units. */
+export interface PathCommand {
+  kind: PathCommandKind
+  x?: number
+  y?: number
+  x1?: number
+  y1?: number
+  x2?: number
+  y2?: number
+}
+/** An outline of a glyph. */
+export interface GlyphOutline {
+  /** Path commands describing the outline. */
+  commands: Array<PathCommand>
+  /** A tight bounding box of the outline. */
+  bbox: Rect
+}
+/** Options of `glyphToSvgPath`. */
+export interface SvgPathOptions {
+  /** Number of decimal places of the coordinates, up to 20. Defaults to 2. */
+  precision?: number
+  /**
+   * Flips the y-axis so the path can be drawn in SVG's y-down coordinate
+   * system, with the baseline at `y = 0`. Defaults to `true`.
+   */
+  flipY?: boolean
+  /** Font size to scale the path to. Font units are used when not set. */
+  size?: number
+}
+/** Options of `textToSvg`. */
+export interface SvgTextOptions {
+  /** Number of decimal places of the coordinates, up to 20. Defaults to 2. */
+  precision?: number
+  /** Fill of the text path. Defaults to `black`. */
+  fill?: string
+}
+/** An inclusive range of Unicode codepoints. */
+export interface CodepointRange {
+  /** First codepoint of the range. */
+  start: number
+  /** Last codepoint of the range. */
+  end: number
+}
 export const enum IndexToLocationFormat {
   Short = 0,
   Long = 1
@@ -170,6 +659,12 @@ export interface Tables {
   maxp: Table
   os2?: Table
   name?: Table
+  /** A [Character to Glyph Index Mapping Table](https://docs.microsoft.com/en-us/typography/opentype/spec/cmap). */
+  cmap?: CMAPTable
+  /** A [PostScript Table](https://docs.microsoft.com/en-us/typography/opentype/spec/post). */
+  post?: POSTTable
+  /** A [Font Variations Table](https://docs.microsoft.com/en-us/typography/opentype/spec/fvar). */
+  fvar?: FVARTable
 }
 export interface LineMetrics {
   /** Line position. */
@@ -201,6 +696,15 @@ export interface Rect {
   xMax: number
   yMax: number
 }
+/** The outcome of parsing a single face of a font collection. */
+export interface ParsedFace {
+  /** The face index inside the collection. */
+  index: number
+  /** The parsed face, `undefined` when parsing failed. */
+  parser?: TTFParser
+  /** Why the face could not be parsed, `undefined` on success. */
+  error?: FaceError
+}
 export class TTFParser {
   /**
    * Checks that face is marked as *Bold*.
@@ -345,8 +849,17 @@ export class TTFParser {
    * Returns face's italic angle.
    *
    * Returns `undefined` when `post` table is not present.
-   * Returns face permissions.
    */
+  italicAngle?: number
+  /**
+   * Returns face's underline metrics.
+   *
+   * This property is affected by variation axes.
+   *
+   * Returns `undefined` when `post` table is not present.
+   */
+  underlineMetrics?: LineMetrics
+  /** Returns face permissions. */
   permissions?: Permissions
   /** Checks if the face subsetting is allowed. */
   isSubsettingAllowed: boolean
@@ -370,16 +883,351 @@ export class TTFParser {
    * Creates a new `TTFParser` from a raw data.
    *
    * `index` indicates the specific font face in a font collection.
-   * Use [`fonts_in_collection`] to get the total number of font faces.
+   * Use [`fonts_in_collection`](TTFParser::fonts_in_collection) to get the total number of font faces.
    * Defaults to 0 if not set.
    *
    * Required tables: `head`, `hhea` and `maxp`.
    *
    * If an optional table has invalid data it will be skipped.
+   *
+   * WOFF and WOFF2 data is decompressed before parsing.
+   *
+   * Throws an error with a `code` of `MalformedFont`, `UnknownMagic`,
+   * `FaceIndexOutOfBounds`, `NoHeadTable`, `NoHheaTable`, `NoMaxpTable`
+   * or `MalformedWoff` when the face cannot be parsed.
    */
   constructor(buffer: Buffer, index?: number | undefined | null)
+  /**
+   * Same as the constructor, but parses the face on the libuv threadpool
+   * instead of blocking the event loop.
+   *
+   * Rejects with the same errors the constructor throws.
+   */
+  static parseAsync(buffer: Buffer, index?: number | undefined | null): Promise<TTFParser>
+  /**
+   * Parses the first face of every buffer on the libuv threadpool.
+   *
+   * Results are returned in the same order as `buffers`, with failures
+   * reported through `error` instead of rejecting the whole batch.
+   */
+  static parseAsyncBatch(buffers: Array<Buffer>): Promise<Array<ParsedFace>>
+  /**
+   * Creates a new `TTFParser` from a font file.
+   *
+   * The file is memory-mapped instead of being read into the JS heap and
+   * stays mapped for the lifetime of the parser.
+   *
+   * Throws an error with a `code` of `Io` when the file cannot be read,
+   * otherwise behaves like the constructor.
+   */
+  static fromFile(path: string, index?: number | undefined | null): TTFParser
+  /**
+   * Same as [`from_file`](TTFParser::from_file), but maps and parses the
+   * file on the libuv threadpool.
+   */
+  static fromFileAsync(path: string, index?: number | undefined | null): Promise<TTFParser>
+  /**
+   * Returns the number of fonts stored in a TrueType font collection.
+   *
+   * Returns `null` if the provided data is not a TrueType font collection.
+   *
+   * WOFF and WOFF2 data is decompressed first, the same way as
+   * [`parse_all`](TTFParser::parse_all) does it. Throws an error with a
+   * `code` of `MalformedWoff` when it cannot be decompressed.
+   */
+  static fontsInCollection(buffer: Buffer): number | null
+  /**
+   * Parses every face stored in the provided data.
+   *
+   * A single font file is treated as a collection of one face.
+   * Faces that fail to parse are reported through `error` instead of
+   * aborting the whole file.
+   */
+  static parseAll(buffer: Buffer): Array<ParsedFace>
   /** Returns a bounding box that large enough to enclose any glyph from the face. */
   get globalBoundingBox(): Rect
   get tables(): Tables
+  /**
+   * Returns the [Glyph Substitution Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub),
+   * or `null` when the face has none.
+   */
+  gsubTable(): LayoutTable | null
+  /**
+   * Returns the [Glyph Positioning Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos),
+   * or `null` when the face has none.
+   */
+  gposTable(): LayoutTable | null
+  /**
+   * Returns the [Glyph Definition Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef),
+   * or `null` when the face has none.
+   */
+  gdefTable(): GDEFTable | null
+  /**
+   * Returns the [Style Attributes Table](https://docs.microsoft.com/en-us/typography/opentype/spec/stat),
+   * or `null` when the face has none.
+   */
+  statTable(): STATTable | null
+  /**
+   * Sets a variation axis coordinate, e.g. `setVariation('wght', 700)`.
+   *
+   * The value is in user space and clamped to the range of the axis.
+   * Metric properties, glyph metrics and outlines are updated to the new
+   * coordinates, using the `HVAR`, `MVAR`, `gvar` and `CFF2` tables.
+   *
+   * Only the first 32 axes of a face can be set.
+   *
+   * Throws an error with a `code` of `UnknownVariationAxis` when the face
+   * has no such axis.
+   */
+  setVariation(tag: string, value: number): void
+  /**
+   * Sets several variation axis coordinates at once, e.g.
+   * `setVariations({ wght: 700, wdth: 75 })`.
+   *
+   * Axes that are not listed keep their current coordinates.
+   * Nothing is changed when one of the axes does not exist.
+   *
+   * Throws an error with a `code` of `UnknownVariationAxis` when the face
+   * has no such axis.
+   */
+  setVariations(variations: Record<string, number>): void
+  /**
+   * Sets the coordinates of every axis to the ones of a named instance of
+   * the `fvar` table.
+   *
+   * Throws an error with a `code` of `NamedInstanceOutOfBounds` when the
+   * index is larger than the number of named instances.
+   */
+  setNamedInstance(index: number): void
+  /**
+   * Returns the current normalized variation coordinates, in the -1..1
+   * range and in the order of the `fvar` axes.
+   *
+   * Coordinates are quantized to F2DOT14 and already mapped through the
+   * `avar` table, like they are used to vary the face.
+   *
+   * Returns an empty array for non-variable faces.
+   */
+  get normalizedCoordinates(): Array<number>
+  /**
+   * Converts a user-space axis value, e.g. `700` for `wght`, to a normalized
+   * coordinate in the -1..1 range.
+   *
+   * The value is clamped to the range of the axis, mapped through the
+   * `avar` table and quantized to F2DOT14, the same way the coordinates
+   * set by [`set_variation`](TTFParser::set_variation) are computed.
+   *
+   * Throws an error with a `code` of `UnknownVariationAxis` when the face
+   * has no such axis.
+   */
+  normalizeVariation(tag: string, value: number): number
+  /**
+   * Converts a normalized coordinate in the -1..1 range back to a
+   * user-space axis value, undoing the `avar` mapping.
+   *
+   * Throws an error with a `code` of `UnknownVariationAxis` when the face
+   * has no such axis.
+   */
+  denormalizeVariation(tag: string, coordinate: number): number
+  /**
+   * Resolves a Unicode codepoint to a glyph index.
+   *
+   * All subtables of the `cmap` table will be checked.
+   *
+   * Returns `null` when the codepoint is not mapped.
+   */
+  glyphIndex(codepoint: number): number | null
+  /**
+   * Resolves every character of `text` to a glyph index.
+   *
+   * Returns one entry per Unicode codepoint, `null` for unmapped ones.
+   */
+  glyphIndexMany(text: string): Array<number | undefined | null>
+  /**
+   * Resolves a variation of a glyph index from two codepoints.
+   *
+   * Uses the Unicode Variation Sequences subtable (format 14) of the `cmap` table.
+   *
+   * Returns `null` when the sequence is not mapped.
+   */
+  glyphVariationIndex(codepoint: number, variationSelector: number): number | null
+  /**
+   * Returns the name of a glyph.
+   *
+   * Uses the `post` table and falls back to the charset of the `CFF` table.
+   *
+   * Returns `null` when no name is associated with the glyph.
+   */
+  glyphName(glyphId: number): string | null
+  /**
+   * Resolves a glyph name, like `uni0041` or `f_f_i`, to a glyph index.
+   *
+   * Uses the `post` table and falls back to the charset of the `CFF` table.
+   *
+   * Returns `null` when no glyph has this name.
+   */
+  glyphIdByName(name: string): number | null
+  /**
+   * Returns the name of every glyph, indexed by glyph id.
+   *
+   * Uses the `post` table and falls back to the charset of the `CFF` table,
+   * like [`glyph_name`](TTFParser::glyph_name). Glyphs without a name are
+   * represented by an empty string.
+   */
+  glyphNames(): Array<string>
+  /**
+   * Returns every codepoint mapped by the Unicode subtables of the `cmap`
+   * table, as sorted and merged inclusive ranges.
+   *
+   * Returns an empty array when the `cmap` table is not present.
+   */
+  codepointRanges(): Array<CodepointRange>
+  /**
+   * Returns every codepoint mapped to `glyph_id` by the Unicode subtables of
+   * the `cmap` table, in ascending order.
+   *
+   * A glyph may be mapped by several codepoints, or by none at all.
+   */
+  codepointsForGlyph(glyphId: number): Array<number>
+  /**
+   * Builds a reverse mapping of the Unicode subtables of the `cmap` table.
+   *
+   * The returned array is indexed by glyph id and holds the codepoints
+   * mapped to each glyph, in ascending order.
+   */
+  glyphToUnicodeMap(): Array<Array<number>>
+  /**
+   * Returns the class of every glyph from the `GDEF` table, indexed by
+   * glyph id.
+   *
+   * Returns `null` when the table has no glyph class definition.
+   */
+  glyphClasses(): Array<GlyphClass> | null
+  /**
+   * Returns the mark attachment class of every glyph from the `GDEF` table,
+   * indexed by glyph id, `0` for glyphs without one.
+   *
+   * Returns `null` when the table has no mark attachment class definition.
+   */
+  markAttachmentClasses(): Array<number> | null
+  /**
+   * Returns the sorted, deduplicated tags of the features of the `GSUB` and
+   * `GPOS` tables, e.g. `["kern", "liga", "ss01"]`.
+   */
+  featureTags(): Array<string>
+  /**
+   * Returns the alternate glyphs of a character from the `GSUB` table.
+   *
+   * Alternates come from the single and alternate substitutions of the
+   * `aalt`, `salt`, `swsh`, `cswh`, `titl`, `nalt`, `hist`, `ornm`, `ss01` to
+   * `ss20` and `cv01` to `cv99` features, sorted by feature tag, then by the
+   * feature value selecting them. Contextual substitutions are not followed.
+   *
+   * Returns an empty array when the codepoint is not mapped.
+   */
+  alternatesFor(codepoint: number): Array<GlyphAlternate>
+  /**
+   * Returns the ligature substitutions of the `GSUB` table, e.g. `f` `i`
+   * to `fi`.
+   *
+   * Only the feature tagged `feature_tag` is considered when set, like
+   * `liga`, `dlig`, `hlig`, `rlig` or a custom feature, and every feature
+   * otherwise. Ligatures are sorted by feature tag, then components.
+   * Contextual substitutions are not followed.
+   */
+  ligatures(featureTag?: string | undefined | null): Array<LigatureSubstitution>
+  /**
+   * Outlines a glyph and returns its path commands and tight bounding box.
+   *
+   * Works with `glyf`, `CFF` and `CFF2` based faces.
+   *
+   * Returns `null` when the glyph has no outline (like a space) or when
+   * the glyph id is out of range.
+   */
+  outlineGlyph(glyphId: number): GlyphOutline | null
+  /**
+   * Returns horizontal and vertical metrics and the bounding box of a glyph.
+   *
+   * This method is affected by variation axes.
+   *
+   * Returns `null` when the glyph id is out of range.
+   */
+  glyphMetrics(glyphId: number): GlyphMetrics | null
+  /**
+   * Returns the metrics of every glyph as typed arrays indexed by glyph id.
+   *
+   * This method is affected by variation axes.
+   */
+  allGlyphMetrics(): GlyphMetricsArrays
+  /**
+   * Returns the horizontal kerning between two glyphs in font units, or `0`
+   * when the pair isn't kerned.
+   *
+   * Pair adjustments of the `kern` feature of the `GPOS` table are used,
+   * including class-based ones. Fonts without one fall back to the legacy
+   * `kern` table, then to the AAT `kerx` table. Only the advance of the left
+   * glyph is adjusted and `GPOS` variation deltas are not applied.
+   */
+  kerning(leftGlyph: number, rightGlyph: number): number
+  /**
+   * Returns every kerned pair of glyphs as parallel typed arrays, sorted by
+   * left glyph id, then right glyph id.
+   *
+   * Values are the same as `kerning`, but class-based subtables of the
+   * legacy `kern` and `kerx` tables are not listed.
+   */
+  kerningPairs(): KerningPairs
+  /**
+   * Measures `text` laid out on a single line at `font_size`.
+   *
+   * Characters are mapped through the `cmap` table, advances come from the
+   * `hmtx` table and pair kerning from the legacy `kern` table. No shaping
+   * is done, so ligatures and GPOS kerning are not taken into account.
+   *
+   * This method is affected by variation axes.
+   */
+  measureText(text: string, fontSize: number, options?: MeasureTextOptions | undefined | null): TextMeasurement
+  /**
+   * Shapes `text` as a single run and returns positioned glyphs in visual
+   * order.
+   *
+   * Runs a full OpenType shaping pipeline: characters are mapped through
+   * the `cmap` table, substituted by the `GSUB` table and positioned by the
+   * `GPOS` table (or the `kern` and `morx` tables when missing), for the
+   * script, language and features in `options`.
+   *
+   * This method is affected by variation axes.
+   *
+   * Throws an error with a `code` of `InvalidShapeOption` when the script,
+   * language, direction or one of the features cannot be parsed.
+   */
+  shape(text: string, options?: ShapeOptions | undefined | null): Array<ShapedGlyph>
+  /**
+   * Converts a glyph outline to SVG path data (the `d` attribute).
+   *
+   * Returns `null` when the glyph has no outline or when the glyph id is
+   * out of range.
+   */
+  glyphToSvgPath(glyphId: number, options?: SvgPathOptions | undefined | null): string | null
+  /**
+   * Renders `text` at the font `size` as a standalone `<svg>` document with
+   * the glyphs converted to a single path.
+   *
+   * Glyphs are positioned with their horizontal advances only, without
+   * kerning or shaping. Unmapped characters are drawn with the `.notdef` glyph.
+   */
+  textToSvg(text: string, size: number, options?: SvgTextOptions | undefined | null): string
+  /**
+   * Returns the extended metadata XML of a WOFF or WOFF2 file.
+   *
+   * Returns `null` when the font wasn't a web font or has no metadata.
+   */
+  get woffMetadata(): string | null
+  /**
+   * Returns the private data block of a WOFF or WOFF2 file.
+   *
+   * Returns `null` when the font wasn't a web font or has no private data.
+   */
+  get woffPrivateData(): Buffer | null
   get toJSON(): string
 }
@@ -252,8 +252,9 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { IndexToLocationFormat, NameId, PlatformId, Style, Permissions, Width, Weight, TTFParser } = nativeBinding
+const { GlyphClass, IndexToLocationFormat, NameId, PlatformId, Style, Permissions, Width, Weight, PathCommandKind, TTFParser } = nativeBinding
 
+module.exports.GlyphClass = GlyphClass
 module.exports.IndexToLocationFormat = IndexToLocationFormat
 module.exports.NameId = NameId
 module.exports.PlatformId = PlatformId
@@ -261,4 +262,5 @@ module.exports.Style = Style
 module.exports.Permissions = Permissions
 module.exports.Width = Width
 module.exports.Weight = Weight
+module.exports.PathCommandKind = PathCommandKind
 module.exports.TTFParser = TTFParser
@@ -0,0 +1,70 @@
+use std::{fs::File, ops::Deref, path::Path, sync::Arc};
+
+use memmap2::Mmap;
+
+use crate::{woff, CustomError};
+
+/// Raw font data kept alive for the lifetime of a `TTFParser`.
+pub enum FontData {
+  /// A copy of the data passed in from JS.
+  Owned(Vec<u8>),
+
+  /// A memory-mapped font file.
+  Mapped(Mmap),
+
+  /// A font decoded from WOFF or WOFF2 data.
+  Woff(woff::WebFont),
+}
+
+impl FontData {
+  /// Copies `data` out of the JS heap, so the parser doesn't depend on the
+  /// lifetime of the originating `Buffer`.
+  ///
+  /// WOFF and WOFF2 data is decoded instead of copied.
+  pub fn from_slice(data: &[u8]) -> Result<Arc<FontData>, CustomError> {
+    let data = match woff::decode(data)? {
+      Some(font) => FontData::Woff(font),
+      None => FontData::Owned(data.to_vec()),
+    };
+
+    Ok(Arc::new(data))
+  }
+
+  /// Memory-maps the file at `path`.
+  ///
+  /// WOFF and WOFF2 files are decoded and unmapped right away.
+  pub fn map(path: impl AsRef<Path>) -> Result<Arc<FontData>, CustomError> {
+    let file = File::open(path)?;
+    // SAFETY: the mapping is read-only. Truncating or rewriting the font file
+    // while a parser still references it is undefined behaviour, the same
+    // caveat every memory-mapped reader has.
+    let mmap = unsafe { Mmap::map(&file)? };
+
+    let data = match woff::decode(&mmap)? {
+      Some(font) => FontData::Woff(font),
+      None => FontData::Mapped(mmap),
+    };
+
+    Ok(Arc::new(data))
+  }
+
+  /// Returns the decoded web font, when the data came from a WOFF or WOFF2 file.
+  pub fn web_font(&self) -> Option<&woff::WebFont> {
+    match self {
+      FontData::Woff(font) => Some(font),
+      _ => None,
+    }
+  }
+}
+
+impl Deref for FontData {
+  type Target = [u8];
+
+  fn deref(&self) -> &[u8] {
+    match self {
+      FontData::Owned(data) => data,
+      FontData::Mapped(mmap) => mmap,
+      FontData::Woff(font) => &font.sfnt,
+    }
+  }
+}
@@ -0,0 +1,134 @@
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use ttf_parser::FaceParsingError;
+
+/// Errors thrown by `TTFParser`.
+///
+/// The variant name is exposed as the `code` property of the thrown JS error,
+/// so callers can tell apart why a font was rejected.
+pub enum CustomError {
+  /// The font file could not be opened or read.
+  Io(std::io::Error),
+
+  /// An attempt to read out of bounds detected.
+  MalformedFont,
+
+  /// Face data doesn't start with a known sfnt or collection signature.
+  UnknownMagic,
+
+  /// The face index is larger than the number of faces in the font.
+  FaceIndexOutOfBounds,
+
+  /// The `head` table is missing or malformed.
+  NoHeadTable,
+
+  /// The `hhea` table is missing or malformed.
+  NoHheaTable,
+
+  /// The `maxp` table is missing or malformed.
+  NoMaxpTable,
+
+  /// The data has a WOFF or WOFF2 signature but could not be decoded.
+  MalformedWoff,
+
+  /// The face has no variation axis with the given tag, or the axis is past
+  /// the first 32 ones that coordinates can be set for.
+  UnknownVariationAxis(String),
+
+  /// The named instance index is larger than the number of named instances.
+  NamedInstanceOutOfBounds,
+
+  /// A script, language, direction or feature passed to `shape` is invalid.
+  InvalidShapeOption(String),
+}
+
+impl CustomError {
+  /// A human-readable description of the error.
+  pub fn reason(&self) -> String {
+    match self {
+      CustomError::Io(e) => format!("Could not read the font file: {}", e),
+      CustomError::MalformedFont => "The font is malformed".to_owned(),
+      CustomError::UnknownMagic => {
+        "The data is not a TrueType, OpenType or font collection file".to_owned()
+      }
+      CustomError::FaceIndexOutOfBounds => {
+        "The face index is larger than the number of faces in the font".to_owned()
+      }
+      CustomError::NoHeadTable => "The required `head` table is missing or malformed".to_owned(),
+      CustomError::NoHheaTable => "The required `hhea` table is missing or malformed".to_owned(),
+      CustomError::NoMaxpTable => "The required `maxp` table is missing or malformed".to_owned(),
+      CustomError::MalformedWoff => "The WOFF or WOFF2 data could not be decoded".to_owned(),
+      CustomError::UnknownVariationAxis(tag) => {
+        format!("The face has no variation axis with the tag `{}`", tag)
+      }
+      CustomError::NamedInstanceOutOfBounds => {
+        "The named instance index is larger than the number of named instances".to_owned()
+      }
+      CustomError::InvalidShapeOption(option) => format!("Invalid shaping {}", option),
+    }
+  }
+}
+
+impl AsRef<str> for CustomError {
+  fn as_ref(&self) -> &str {
+    match self {
+      CustomError::Io(_) => "Io",
+      CustomError::MalformedFont => "MalformedFont",
+      CustomError::UnknownMagic => "UnknownMagic",
+      CustomError::FaceIndexOutOfBounds => "FaceIndexOutOfBounds",
+      CustomError::NoHeadTable => "NoHeadTable",
+      CustomError::NoHheaTable => "NoHheaTable",
+      CustomError::NoMaxpTable => "NoMaxpTable",
+      CustomError::MalformedWoff => "MalformedWoff",
+      CustomError::UnknownVariationAxis(_) => "UnknownVariationAxis",
+      CustomError::NamedInstanceOutOfBounds => "NamedInstanceOutOfBounds",
+      CustomError::InvalidShapeOption(_) => "InvalidShapeOption",
+    }
+  }
+}
+
+impl From<FaceParsingError> for CustomError {
+  fn from(err: FaceParsingError) -> CustomError {
+    match err {
+      FaceParsingError::MalformedFont => CustomError::MalformedFont,
+      FaceParsingError::UnknownMagic => CustomError::UnknownMagic,
+      FaceParsingError::FaceIndexOutOfBounds => CustomError::FaceIndexOutOfBounds,
+      FaceParsingError::NoHeadTable => CustomError::NoHeadTable,
+      FaceParsingError::NoHheaTable => CustomError::NoHheaTable,
+      FaceParsingError::NoMaxpTable => CustomError::NoMaxpTable,
+    }
+  }
+}
+
+impl From<std::io::Error> for CustomError {
+  fn from(err: std::io::Error) -> CustomError {
+    CustomError::Io(err)
+  }
+}
+
+impl From<CustomError> for Error<CustomError> {
+  fn from(err: CustomError) -> Error<CustomError> {
+    let reason = err.reason();
+    Error::new(err, reason)
+  }
+}
+
+/// An error collected while parsing a single face, instead of being thrown.
+#[napi(object)]
+#[derive(Clone)]
+pub struct FaceError {
+  /// Same value as the `code` property of a thrown error.
+  pub code: String,
+
+  /// A human-readable description of the error.
+  pub message: String,
+}
+
+impl From<CustomError> for FaceError {
+  fn from(err: CustomError) -> FaceError {
+    FaceError {
+      code: err.as_ref().to_owned(),
+      message: err.reason(),
+    }
+  }
+}
@@ -0,0 +1,298 @@
+use std::collections::{BTreeMap, HashMap};
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use ttf_parser::gpos::{PairAdjustment, PositioningSubtable};
+use ttf_parser::opentype_layout::Lookup;
+use ttf_parser::{kern, kerx, Face, GlyphId, Tag};
+
+use crate::tables::layout::coverage_glyphs;
+
+/// Every kerning pair of a face, sorted by left glyph id, then right glyph id.
+///
+/// The arrays are parallel: the pair at index `i` is made of
+/// `leftGlyphs[i]` and `rightGlyphs[i]`, adjusted by `values[i]`.
+#[napi(object)]
+pub struct KerningPairs {
+  /// Glyph ids of the left glyphs.
+  pub left_glyphs: Uint16Array,
+
+  /// Glyph ids of the right glyphs.
+  pub right_glyphs: Uint16Array,
+
+  /// Adjustments of the horizontal advance of the left glyphs, in font units.
+  pub values: Int16Array,
+}
+
+/// The table kerning is read from.
+///
+/// Like shapers do, a single table is used: `GPOS` when it has a `kern`
+/// feature, the legacy `kern` table otherwise, and finally the AAT `kerx`
+/// table.
+enum Source<'a> {
+  Gpos(Vec<Lookup<'a>>),
+  Kern(kern::Table<'a>),
+  Kerx(kerx::Table<'a>),
+}
+
+impl<'a> Source<'a> {
+  fn new(face: &Face<'a>) -> Option<Self> {
+    let tables = face.tables();
+    if let Some(lookups) = tables.gpos.and_then(|gpos| {
+      let mut indices: Vec<u16> = gpos
+        .features
+        .into_iter()
+        .filter(|feature| feature.tag == Tag::from_bytes(b"kern"))
+        .flat_map(|feature| feature.lookup_indices)
+        .collect();
+      if indices.is_empty() {
+        return None;
+      }
+
+      // Lookups are applied in list order, once each.
+      indices.sort_unstable();
+      indices.dedup();
+      Some(
+        indices
+          .into_iter()
+          .filter_map(|i| gpos.lookups.get(i))
+          .collect(),
+      )
+    }) {
+      return Some(Source::Gpos(lookups));
+    }
+
+    if let Some(kern) = tables.kern.filter(|kern| {
+      kern
+        .subtables
+        .into_iter()
+        .any(|subtable| is_kern_subtable_used(&subtable))
+    }) {
+      return Some(Source::Kern(kern));
+    }
+
+    tables
+      .kerx
+      .filter(|kerx| {
+        kerx
+          .subtables
+          .into_iter()
+          .any(|subtable| is_kerx_subtable_used(&subtable))
+      })
+      .map(Source::Kerx)
+  }
+}
+
+/// Returns the horizontal kerning between two glyphs, or `0` when the pair
+/// isn't kerned.
+///
+/// `GPOS` pair adjustments of the `kern` feature are used first, including
+/// class-based ones. Only the advance of the first glyph is taken into
+/// account and variation deltas are not applied. Without a `kern` feature,
+/// the legacy `kern` table is used, then the AAT `kerx` table.
+pub fn kerning(face: &Face, left: GlyphId, right: GlyphId) -> i16 {
+  match Source::new(face) {
+    Some(Source::Gpos(lookups)) => lookups.iter().fold(0i16, |value, lookup| {
+      let kerning = lookup
+        .subtables
+        .into_iter::<PositioningSubtable>()
+        .find_map(|subtable| match subtable {
+          PositioningSubtable::Pair(pair) => pair_adjustment(&pair, left, right),
+          _ => None,
+        });
+      value.saturating_add(kerning.unwrap_or(0))
+    }),
+    Some(Source::Kern(_)) => kern_table(face, left, right).unwrap_or(0),
+    Some(Source::Kerx(kerx)) => kerx
+      .subtables
+      .into_iter()
+      .filter(is_kerx_subtable_used)
+      .filter_map(|subtable| subtable.glyphs_kerning(left, right))
+      .fold(0, i16::saturating_add),
+    None => 0,
+  }
+}
+
+/// Returns every pair with a non-zero kerning, with the same values as
+/// [`kerning`].
+///
+/// Class-based subtables of the legacy `kern` and `kerx` tables cannot be
+/// listed and are skipped.
+pub fn kerning_pairs(face: &Face) -> KerningPairs {
+  let mut pairs = BTreeMap::new();
+  let mut add = |left: GlyphId, right: GlyphId, value: i16| {
+    let entry: &mut i16 = pairs.entry((left.0, right.0)).or_default();
+    *entry = entry.saturating_add(value);
+  };
+
+  match Source::new(face) {
+    Some(Source::Gpos(lookups)) => {
+      let glyph_count = face.number_of_glyphs();
+      for lookup in lookups {
+        let subtables: Vec<PairAdjustment> = lookup
+          .subtables
+          .into_iter::<PositioningSubtable>()
+          .filter_map(|subtable| match subtable {
+            PositioningSubtable::Pair(pair) => Some(pair),
+            _ => None,
+          })
+          .collect();
+
+        for (i, subtable) in subtables.iter().enumerate() {
+          // A pair is adjusted by the first subtable of a lookup matching it.
+          let is_shadowed = |left, right| {
+            subtables[..i]
+              .iter()
+              .any(|earlier| pair_adjustment(earlier, left, right).is_some())
+          };
+
+          subtable_pairs(subtable, glyph_count, |left, right, value| {
+            if value != 0 && !is_shadowed(left, right) {
+              add(left, right, value);
+            }
+          });
+        }
+      }
+    }
+    Some(Source::Kern(kern)) => {
+      for subtable in kern.subtables {
+        if let kern::Format::Format0(format0) = &subtable.format {
+          if is_kern_subtable_used(&subtable) {
+            for pair in format0.pairs {
+              add(pair.left(), pair.right(), pair.value);
+            }
+          }
+        }
+      }
+    }
+    Some(Source::Kerx(kerx)) => {
+      for subtable in kerx.subtables {
+        if let kerx::Format::Format0(format0) = &subtable.format {
+          if is_kerx_subtable_used(&subtable) {
+            for pair in format0.pairs {
+              add(pair.left(), pair.right(), pair.value);
+            }
+          }
+        }
+      }
+    }
+    None => {}
+  }
+
+  let mut left_glyphs = Vec::with_capacity(pairs.len());
+  let mut right_glyphs = Vec::with_capacity(pairs.len());
+  let mut values = Vec::with_capacity(pairs.len());
+  for ((left, right), value) in pairs {
+    if value != 0 {
+      left_glyphs.push(left);
+      right_glyphs.push(right);
+      values.push(value);
+    }
+  }
+
+  KerningPairs {
+    left_glyphs: Uint16Array::new(left_glyphs),
+    right_glyphs: Uint16Array::new(right_glyphs),
+    values: Int16Array::new(values),
+  }
+}
+
+/// Looks up a pair adjustment in the legacy `kern` table.
+///
+/// Only horizontal, non-variable subtables without cross-stream kerning are
+/// used. Adjustments of several subtables are summed up, the same way
+/// FreeType does it.
+pub fn kern_table(face: &Face, left: GlyphId, right: GlyphId) -> Option<i16> {
+  let kern = face.tables().kern?;
+
+  let mut value: Option<i16> = None;
+  for subtable in kern.subtables {
+    if !is_kern_subtable_used(&subtable) {
+      continue;
+    }
+
+    if let Some(kerning) = subtable.glyphs_kerning(left, right) {
+      value = Some(value.unwrap_or(0).saturating_add(kerning));
+    }
+  }
+
+  value
+}
+
+fn is_kern_subtable_used(subtable: &kern::Subtable) -> bool {
+  subtable.horizontal && !subtable.variable && !subtable.has_cross_stream
+}
+
+fn is_kerx_subtable_used(subtable: &kerx::Subtable) -> bool {
+  subtable.horizontal && !subtable.variable && !subtable.has_cross_stream
+}
+
+/// Returns the advance adjustment of the first glyph of a pair, or `None`
+/// when the subtable doesn't match the pair.
+fn pair_adjustment(subtable: &PairAdjustment, left: GlyphId, right: GlyphId) -> Option<i16> {
+  match subtable {
+    PairAdjustment::Format1 { coverage, sets } => {
+      let index = coverage.get(left)?;
+      let (record, _) = sets.get(index)?.get(right)?;
+      Some(record.x_advance)
+    }
+    PairAdjustment::Format2 {
+      coverage,
+      classes,
+      matrix,
+    } => {
+      coverage.get(left)?;
+      let (record, _) = matrix.get((classes.0.get(left), classes.1.get(right)))?;
+      Some(record.x_advance)
+    }
+  }
+}
+
+/// Calls `f` with every pair a subtable matches and its advance adjustment.
+fn subtable_pairs(
+  subtable: &PairAdjustment,
+  glyph_count: u16,
+  mut f: impl FnMut(GlyphId, GlyphId, i16),
+) {
+  let glyphs = coverage_glyphs(subtable.coverage(), glyph_count);
+  match subtable {
+    PairAdjustment::Format1 { coverage, sets } => {
+      for left in glyphs {
+        let Some(set) = coverage.get(left).and_then(|index| sets.get(index)) else {
+          continue;
+        };
+        for right in (0..glyph_count).map(GlyphId) {
+          if let Some((record, _)) = set.get(right) {
+            f(left, right, record.x_advance);
+          }
+        }
+      }
+    }
+    PairAdjustment::Format2 {
+      classes, matrix, ..
+    } => {
+      // Classes are shared by many glyphs, so look up every row only once.
+      let right_classes: Vec<u16> = (0..glyph_count)
+        .map(|id| classes.1.get(GlyphId(id)))
+        .collect();
+      let class_count = right_classes
+        .iter()
+        .max()
+        .map_or(0, |&max| usize::from(max) + 1);
+      let mut rows: HashMap<u16, Vec<Option<i16>>> = HashMap::new();
+      for left in glyphs {
+        let left_class = classes.0.get(left);
+        let row = rows.entry(left_class).or_insert_with(|| {
+          (0..class_count as u16)
+            .map(|right_class| Some(matrix.get((left_class, right_class))?.0.x_advance))
+            .collect()
+        });
+        for (right, &right_class) in right_classes.iter().enumerate() {
+          if let Some(value) = row[usize::from(right_class)] {
+            f(left, GlyphId(right as u16), value);
+          }
+        }
+      }
+    }
+  }
+}
@@ -1,12 +1,42 @@
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
+use std::sync::Arc;
+
+use data::FontData;
+use kerning::KerningPairs;
+use metrics::{GlyphMetrics, GlyphMetricsArrays};
+use outline::GlyphOutline;
+use shaping::{ShapeOptions, ShapedGlyph};
+use std::collections::HashMap;
+use substitution::{GlyphAlternate, LigatureSubstitution};
+use svg::{SvgPathOptions, SvgTextOptions};
+use tables::cmap::CodepointRange;
+use tables::gdef::GlyphClass;
+use tables::layout::LayoutTable;
 use tables::os2::{Permissions, ScriptMetrics};
-use ttf_parser::Face;
-
+use tables::LineMetrics;
+use text::{MeasureTextOptions, TextMeasurement};
+use ttf_parser::{Face, GlyphId, Tag};
+
+mod data;
+mod error;
+mod kerning;
+mod metrics;
+mod outline;
+mod shaping;
+mod stream;
+mod substitution;
+mod svg;
 mod tables;
+mod task;
+mod text;
+mod variations;
+mod woff;
 
+pub use error::{CustomError, FaceError};
 pub use tables::{Tables, TablesEnum};
+pub use task::{FromFileTask, ParseBatchTask, ParseTask};
 
 // #[napi]
 // pub struct TTFParser {
@@ -14,20 +44,6 @@ pub use tables::{Tables, TablesEnum};
 //   pub tables: Tables,
 // }
 
-pub enum CustomError {
-  NapiError(Error),
-  Panic,
-}
-
-impl AsRef<str> for CustomError {
-  fn as_ref(&self) -> &str {
-    match self {
-      CustomError::Panic => "Panic",
-      CustomError::NapiError(e) => e.status.as_ref(),
-    }
-  }
-}
-
 // #[napi]
 // impl TTFParser {
 //   #[napi(constructor)]
@@ -48,7 +64,14 @@ impl AsRef<str> for CustomError {
 #[napi(js_name = "TTFParser")]
 #[derive(Clone)]
 pub struct TTFParser {
-  tables: Tables,
+  /// Font data the face is re-parsed from on demand.
+  data: Arc<FontData>,
+
+  /// The face index inside `data`.
+  index: u32,
+
+  /// Variation coordinates applied to the face, in the order they were set.
+  variations: Vec<(Tag, f32)>,
 
   /// Checks that face is marked as *Bold*.
   ///
@@ -175,7 +198,14 @@ pub struct TTFParser {
   /// Returns face's italic angle.
   ///
   /// Returns `undefined` when `post` table is not present.
-  // pub italic_angle: Option<f32>,
+  pub italic_angle: Option<f64>,
+
+  /// Returns face's underline metrics.
+  ///
+  /// This property is affected by variation axes.
+  ///
+  /// Returns `undefined` when `post` table is not present.
+  pub underline_metrics: Option<LineMetrics>,
 
   /// Returns face permissions.
   pub permissions: Option<Permissions>,
@@ -199,34 +229,191 @@ pub struct TTFParser {
   pub superscript_metrics: Option<ScriptMetrics>,
 }
 
+/// The outcome of parsing a single face of a font collection.
+#[napi(object, object_from_js = false)]
+pub struct ParsedFace {
+  /// The face index inside the collection.
+  pub index: u32,
+
+  /// The parsed face, `undefined` when parsing failed.
+  pub parser: Option<TTFParser>,
+
+  /// Why the face could not be parsed, `undefined` on success.
+  pub error: Option<FaceError>,
+}
+
+impl ParsedFace {
+  fn new(index: u32, result: std::result::Result<TTFParser, CustomError>) -> Self {
+    match result {
+      Ok(parser) => Self {
+        index,
+        parser: Some(parser),
+        error: None,
+      },
+      Err(err) => Self {
+        index,
+        parser: None,
+        error: Some(err.into()),
+      },
+    }
+  }
+}
+
 #[napi]
 impl TTFParser {
   /// Creates a new `TTFParser` from a raw data.
   ///
   /// `index` indicates the specific font face in a font collection.
-  /// Use [`fonts_in_collection`] to get the total number of font faces.
+  /// Use [`fonts_in_collection`](TTFParser::fonts_in_collection) to get the total number of font faces.
   /// Defaults to 0 if not set.
   ///
   /// Required tables: `head`, `hhea` and `maxp`.
   ///
   /// If an optional table has invalid data it will be skipped.
+  ///
+  /// WOFF and WOFF2 data is decompressed before parsing.
+  ///
+  /// Throws an error with a `code` of `MalformedFont`, `UnknownMagic`,
+  /// `FaceIndexOutOfBounds`, `NoHeadTable`, `NoHheaTable`, `NoMaxpTable`
+  /// or `MalformedWoff` when the face cannot be parsed.
   #[napi(constructor)]
-  pub fn new(env: Env, buffer: Buffer, index: Option<i16>) -> napi::Result<TTFParser> {
-    let face = match Face::parse(buffer.as_ref(), index.unwrap_or_default() as u32) {
-      Ok(font) => font,
-      Err(err) => {
-        return Err(Error::new(Status::GenericFailure, "asd"));
-        // return Err(Status::from_reason(env, "Division by zero is not allowed"))
-      }
+  pub fn new(buffer: Buffer, index: Option<u32>) -> Result<TTFParser, CustomError> {
+    let data = FontData::from_slice(&buffer)?;
+
+    Ok(Self::parse(data, index.unwrap_or_default())?)
+  }
+
+  /// Same as the constructor, but parses the face on the libuv threadpool
+  /// instead of blocking the event loop.
+  ///
+  /// Rejects with the same errors the constructor throws.
+  #[napi]
+  pub fn parse_async(buffer: Buffer, index: Option<u32>) -> AsyncTask<ParseTask> {
+    AsyncTask::new(ParseTask::new(buffer, index.unwrap_or_default()))
+  }
+
+  /// Parses the first face of every buffer on the libuv threadpool.
+  ///
+  /// Results are returned in the same order as `buffers`, with failures
+  /// reported through `error` instead of rejecting the whole batch.
+  #[napi]
+  pub fn parse_async_batch(buffers: Vec<Buffer>) -> AsyncTask<ParseBatchTask> {
+    AsyncTask::new(ParseBatchTask::new(buffers))
+  }
+
+  /// Creates a new `TTFParser` from a font file.
+  ///
+  /// The file is memory-mapped instead of being read into the JS heap and
+  /// stays mapped for the lifetime of the parser.
+  ///
+  /// Throws an error with a `code` of `Io` when the file cannot be read,
+  /// otherwise behaves like the constructor.
+  #[napi]
+  pub fn from_file(path: String, index: Option<u32>) -> Result<TTFParser, CustomError> {
+    Ok(Self::from_file_impl(&path, index.unwrap_or_default())?)
+  }
+
+  /// Same as [`from_file`](TTFParser::from_file), but maps and parses the
+  /// file on the libuv threadpool.
+  #[napi]
+  pub fn from_file_async(path: String, index: Option<u32>) -> AsyncTask<FromFileTask> {
+    AsyncTask::new(FromFileTask::new(path, index.unwrap_or_default()))
+  }
+
+  /// Returns the number of fonts stored in a TrueType font collection.
+  ///
+  /// Returns `null` if the provided data is not a TrueType font collection.
+  ///
+  /// WOFF and WOFF2 data is decompressed first, the same way as
+  /// [`parse_all`](TTFParser::parse_all) does it. Throws an error with a
+  /// `code` of `MalformedWoff` when it cannot be decompressed.
+  #[napi]
+  pub fn fonts_in_collection(buffer: Buffer) -> Result<Option<u32>, CustomError> {
+    // Only the collection header is read, so plain data isn't copied.
+    let count = match woff::decode(&buffer)? {
+      Some(font) => ttf_parser::fonts_in_collection(&font.sfnt),
+      None => ttf_parser::fonts_in_collection(&buffer),
+    };
+
+    Ok(count)
+  }
+
+  /// Parses every face stored in the provided data.
+  ///
+  /// A single font file is treated as a collection of one face.
+  /// Faces that fail to parse are reported through `error` instead of
+  /// aborting the whole file.
+  #[napi]
+  pub fn parse_all(buffer: Buffer) -> Vec<ParsedFace> {
+    let data = match FontData::from_slice(&buffer) {
+      Ok(data) => data,
+      Err(err) => return vec![ParsedFace::new(0, Err(err))],
     };
+    let count = ttf_parser::fonts_in_collection(&data).unwrap_or(1);
 
-    let tables = Tables::new(&face);
-    // log(format!("{:?}", data).as_str());
+    (0..count)
+      .map(|index| ParsedFace::new(index, Self::parse(data.clone(), index)))
+      .collect()
+  }
 
-    let permissions = tables.os2.as_ref().map(|v| v.permissions.to_owned());
+  fn parse(data: Arc<FontData>, index: u32) -> std::result::Result<TTFParser, CustomError> {
+    let face = Face::parse(&data, index)?;
+    let parser = Self::from_face(&face, data.clone(), index);
 
-    let parser = Self {
-      tables,
+    Ok(parser)
+  }
+
+  fn from_file_impl(path: &str, index: u32) -> std::result::Result<TTFParser, CustomError> {
+    Self::parse(FontData::map(path)?, index)
+  }
+
+  /// Re-parses the retained face.
+  ///
+  /// Parsing only reads the table directory, so this is cheap enough to do
+  /// for every query instead of keeping a self-referencing `Face` around.
+  ///
+  /// Variation coordinates set on the parser are applied to the returned face.
+  pub(crate) fn face(&self) -> Face<'_> {
+    let mut face = Face::parse(&self.data, self.index).expect("face was validated on construction");
+    variations::apply(&mut face, &self.variations);
+    face
+  }
+
+  /// Re-reads the metric properties after the variation coordinates changed.
+  fn refresh(&mut self) {
+    let refreshed = Self::from_face(&self.face(), self.data.clone(), self.index);
+    *self = TTFParser {
+      variations: std::mem::take(&mut self.variations),
+      ..refreshed
+    };
+  }
+
+  /// Returns the index of the axis named `tag`.
+  fn axis_position(
+    axes: &variations::Axes,
+    tag: String,
+  ) -> std::result::Result<usize, CustomError> {
+    variations::parse_tag(&tag)
+      .and_then(|t| axes.position(t))
+      .ok_or(CustomError::UnknownVariationAxis(tag))
+  }
+
+  /// Stores a variation coordinate, replacing a previous one for the same axis.
+  fn push_variation(&mut self, tag: Tag, value: f32) {
+    self.variations.retain(|&(t, _)| t != tag);
+    self.variations.push((tag, value));
+  }
+
+  fn from_face(face: &Face, data: Arc<FontData>, index: u32) -> TTFParser {
+    let permissions = face
+      .tables()
+      .os2
+      .map(|os2| Permissions::from(os2.permissions()));
+
+    Self {
+      data,
+      index,
+      variations: Vec::new(),
 
       is_bold: face.is_bold(),
       is_italic: face.is_italic(),
@@ -237,7 +424,7 @@ impl TTFParser {
       is_variable: face.is_variable(),
       // has_non_default_variation_coordinates: face.has_non_default_variation_coordinates(),
       units_per_em: face.units_per_em(),
-      // italic_angle: face.italic_angle(),
+      italic_angle: face.italic_angle().map(f64::from),
       ascender: face.ascender(),
       descender: face.descender(),
       height: face.height(),
@@ -255,11 +442,8 @@ impl TTFParser {
       is_bitmap_embedding_allowed: face.is_bitmap_embedding_allowed(),
       number_of_glyphs: face.number_of_glyphs(),
       superscript_metrics: face.superscript_metrics().map(ScriptMetrics::from),
-      // glyph_ver_advance: face.glyph_ver_advance(),
-      // glyph_hor_advance: face.glyph_hor_advance(),
-    };
-
-    Ok(parser)
+      underline_metrics: face.underline_metrics().map(LineMetrics::from),
+    }
   }
 
   // #[wasm_bindgen(js_name = style)]
@@ -274,13 +458,457 @@ impl TTFParser {
   // #[wasm_bindgen(js_name = globalBoundingBox, method)]
   #[napi(getter)]
   pub fn global_bounding_box(&self) -> tables::Rect {
-    self.tables.head.global_bbox
+    self.face().global_bounding_box().into()
   }
 
   // #[wasm_bindgen(getter)]
   #[napi(getter)]
-  pub fn tables(&mut self) -> Tables {
-    self.tables.to_owned()
+  pub fn tables(&self) -> Tables {
+    Tables::new(&self.face())
+  }
+
+  /// Returns the [Glyph Substitution Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub),
+  /// or `null` when the face has none.
+  #[napi]
+  pub fn gsub_table(&self) -> Option<LayoutTable> {
+    let face = self.face();
+    LayoutTable::new(&face, Tag::from_bytes(b"GSUB"), face.tables().gsub)
+  }
+
+  /// Returns the [Glyph Positioning Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos),
+  /// or `null` when the face has none.
+  #[napi]
+  pub fn gpos_table(&self) -> Option<LayoutTable> {
+    let face = self.face();
+    LayoutTable::new(&face, Tag::from_bytes(b"GPOS"), face.tables().gpos)
+  }
+
+  /// Returns the [Glyph Definition Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef),
+  /// or `null` when the face has none.
+  #[napi]
+  pub fn gdef_table(&self) -> Option<tables::gdef::Table> {
+    tables::gdef::Table::new(&self.face())
+  }
+
+  /// Returns the [Style Attributes Table](https://docs.microsoft.com/en-us/typography/opentype/spec/stat),
+  /// or `null` when the face has none.
+  #[napi]
+  pub fn stat_table(&self) -> Option<tables::stat::Table> {
+    tables::stat::Table::new(&self.face())
+  }
+
+  /// Sets a variation axis coordinate, e.g. `setVariation('wght', 700)`.
+  ///
+  /// The value is in user space and clamped to the range of the axis.
+  /// Metric properties, glyph metrics and outlines are updated to the new
+  /// coordinates, using the `HVAR`, `MVAR`, `gvar` and `CFF2` tables.
+  ///
+  /// Only the first 32 axes of a face can be set.
+  ///
+  /// Throws an error with a `code` of `UnknownVariationAxis` when the face
+  /// has no such axis.
+  #[napi]
+  pub fn set_variation(&mut self, tag: String, value: f64) -> Result<(), CustomError> {
+    self.set_variations(HashMap::from([(tag, value)]))
+  }
+
+  /// Sets several variation axis coordinates at once, e.g.
+  /// `setVariations({ wght: 700, wdth: 75 })`.
+  ///
+  /// Axes that are not listed keep their current coordinates.
+  /// Nothing is changed when one of the axes does not exist.
+  ///
+  /// Throws an error with a `code` of `UnknownVariationAxis` when the face
+  /// has no such axis.
+  #[napi]
+  pub fn set_variations(&mut self, variations: HashMap<String, f64>) -> Result<(), CustomError> {
+    let axes = variations::Axes::new(&self.face());
+    let mut parsed = Vec::with_capacity(variations.len());
+    for (tag, value) in variations {
+      let index = Self::axis_position(&axes, tag)?;
+      parsed.push((axes.tag(index), value as f32));
+    }
+
+    for (tag, value) in parsed {
+      self.push_variation(tag, value);
+    }
+    self.refresh();
+
+    Ok(())
+  }
+
+  /// Sets the coordinates of every axis to the ones of a named instance of
+  /// the `fvar` table.
+  ///
+  /// Throws an error with a `code` of `NamedInstanceOutOfBounds` when the
+  /// index is larger than the number of named instances.
+  #[napi]
+  pub fn set_named_instance(&mut self, index: u32) -> Result<(), CustomError> {
+    let face = self.face();
+    let fvar = tables::fvar::Table::new(&face);
+    let instance = fvar
+      .as_ref()
+      .and_then(|fvar| fvar.instances.get(index as usize))
+      .ok_or(CustomError::NamedInstanceOutOfBounds)?;
+
+    let coordinates = face
+      .variation_axes()
+      .into_iter()
+      .zip(&instance.coordinates)
+      .map(|(axis, &value)| (axis.tag, value as f32))
+      .collect();
+    self.variations = coordinates;
+    self.refresh();
+
+    Ok(())
+  }
+
+  /// Returns the current normalized variation coordinates, in the -1..1
+  /// range and in the order of the `fvar` axes.
+  ///
+  /// Coordinates are quantized to F2DOT14 and already mapped through the
+  /// `avar` table, like they are used to vary the face.
+  ///
+  /// Returns an empty array for non-variable faces.
+  #[napi(getter)]
+  pub fn normalized_coordinates(&self) -> Vec<f64> {
+    self
+      .face()
+      .variation_coordinates()
+      .iter()
+      .map(|c| f64::from(c.get()) / 16384.0)
+      .collect()
+  }
+
+  /// Converts a user-space axis value, e.g. `700` for `wght`, to a normalized
+  /// coordinate in the -1..1 range.
+  ///
+  /// The value is clamped to the range of the axis, mapped through the
+  /// `avar` table and quantized to F2DOT14, the same way the coordinates
+  /// set by [`set_variation`](TTFParser::set_variation) are computed.
+  ///
+  /// Throws an error with a `code` of `UnknownVariationAxis` when the face
+  /// has no such axis.
+  #[napi]
+  pub fn normalize_variation(&self, tag: String, value: f64) -> Result<f64, CustomError> {
+    let axes = variations::Axes::new(&self.face());
+    let index = Self::axis_position(&axes, tag)?;
+
+    Ok(f64::from(axes.normalize(index, value as f32)) / 16384.0)
+  }
+
+  /// Converts a normalized coordinate in the -1..1 range back to a
+  /// user-space axis value, undoing the `avar` mapping.
+  ///
+  /// Throws an error with a `code` of `UnknownVariationAxis` when the face
+  /// has no such axis.
+  #[napi]
+  pub fn denormalize_variation(&self, tag: String, coordinate: f64) -> Result<f64, CustomError> {
+    let axes = variations::Axes::new(&self.face());
+    let index = Self::axis_position(&axes, tag)?;
+
+    Ok(f64::from(axes.denormalize(index, coordinate as f32)))
+  }
+
+  /// Resolves a Unicode codepoint to a glyph index.
+  ///
+  /// All subtables of the `cmap` table will be checked.
+  ///
+  /// Returns `null` when the codepoint is not mapped.
+  #[napi]
+  pub fn glyph_index(&self, codepoint: u32) -> Option<u16> {
+    let c = char::from_u32(codepoint)?;
+    self.face().glyph_index(c).map(|GlyphId(id)| id)
+  }
+
+  /// Resolves every character of `text` to a glyph index.
+  ///
+  /// Returns one entry per Unicode codepoint, `null` for unmapped ones.
+  #[napi]
+  pub fn glyph_index_many(&self, text: String) -> Vec<Option<u16>> {
+    let face = self.face();
+    text
+      .chars()
+      .map(|c| face.glyph_index(c).map(|GlyphId(id)| id))
+      .collect()
+  }
+
+  /// Resolves a variation of a glyph index from two codepoints.
+  ///
+  /// Uses the Unicode Variation Sequences subtable (format 14) of the `cmap` table.
+  ///
+  /// Returns `null` when the sequence is not mapped.
+  #[napi]
+  pub fn glyph_variation_index(&self, codepoint: u32, variation_selector: u32) -> Option<u16> {
+    let c = char::from_u32(codepoint)?;
+    let variation = char::from_u32(variation_selector)?;
+    self
+      .face()
+      .glyph_variation_index(c, variation)
+      .map(|GlyphId(id)| id)
+  }
+
+  /// Returns the name of a glyph.
+  ///
+  /// Uses the `post` table and falls back to the charset of the `CFF` table.
+  ///
+  /// Returns `null` when no name is associated with the glyph.
+  #[napi]
+  pub fn glyph_name(&self, glyph_id: u16) -> Option<String> {
+    self.face().glyph_name(GlyphId(glyph_id)).map(str::to_owned)
+  }
+
+  /// Resolves a glyph name, like `uni0041` or `f_f_i`, to a glyph index.
+  ///
+  /// Uses the `post` table and falls back to the charset of the `CFF` table.
+  ///
+  /// Returns `null` when no glyph has this name.
+  #[napi]
+  pub fn glyph_id_by_name(&self, name: String) -> Option<u16> {
+    self.face().glyph_index_by_name(&name).map(|GlyphId(id)| id)
+  }
+
+  /// Returns the name of every glyph, indexed by glyph id.
+  ///
+  /// Uses the `post` table and falls back to the charset of the `CFF` table,
+  /// like [`glyph_name`](TTFParser::glyph_name). Glyphs without a name are
+  /// represented by an empty string.
+  #[napi]
+  pub fn glyph_names(&self) -> Vec<String> {
+    let face = self.face();
+    (0..face.number_of_glyphs())
+      .map(|id| face.glyph_name(GlyphId(id)).unwrap_or_default().to_owned())
+      .collect()
+  }
+
+  /// Returns every codepoint mapped by the Unicode subtables of the `cmap`
+  /// table, as sorted and merged inclusive ranges.
+  ///
+  /// Returns an empty array when the `cmap` table is not present.
+  #[napi]
+  pub fn codepoint_ranges(&self) -> Vec<CodepointRange> {
+    tables::cmap::codepoint_ranges(&self.face())
+  }
+
+  /// Returns every codepoint mapped to `glyph_id` by the Unicode subtables of
+  /// the `cmap` table, in ascending order.
+  ///
+  /// A glyph may be mapped by several codepoints, or by none at all.
+  #[napi]
+  pub fn codepoints_for_glyph(&self, glyph_id: u16) -> Vec<u32> {
+    tables::cmap::codepoints_for_glyph(&self.face(), glyph_id)
+  }
+
+  /// Builds a reverse mapping of the Unicode subtables of the `cmap` table.
+  ///
+  /// The returned array is indexed by glyph id and holds the codepoints
+  /// mapped to each glyph, in ascending order.
+  #[napi]
+  pub fn glyph_to_unicode_map(&self) -> Vec<Vec<u32>> {
+    tables::cmap::glyph_to_codepoints(&self.face())
+  }
+
+  /// Returns the class of every glyph from the `GDEF` table, indexed by
+  /// glyph id.
+  ///
+  /// Returns `null` when the table has no glyph class definition.
+  #[napi]
+  pub fn glyph_classes(&self) -> Option<Vec<GlyphClass>> {
+    tables::gdef::glyph_classes(&self.face())
+  }
+
+  /// Returns the mark attachment class of every glyph from the `GDEF` table,
+  /// indexed by glyph id, `0` for glyphs without one.
+  ///
+  /// Returns `null` when the table has no mark attachment class definition.
+  #[napi]
+  pub fn mark_attachment_classes(&self) -> Option<Vec<u16>> {
+    tables::gdef::mark_attachment_classes(&self.face())
+  }
+
+  /// Returns the sorted, deduplicated tags of the features of the `GSUB` and
+  /// `GPOS` tables, e.g. `["kern", "liga", "ss01"]`.
+  #[napi]
+  pub fn feature_tags(&self) -> Vec<String> {
+    let face = self.face();
+    let tables = face.tables();
+    let mut tags: Vec<String> = [tables.gsub, tables.gpos]
+      .into_iter()
+      .flatten()
+      .flat_map(|table| table.features.into_iter())
+      .map(|feature| feature.tag.to_string())
+      .collect();
+
+    tags.sort_unstable();
+    tags.dedup();
+    tags
+  }
+
+  /// Returns the alternate glyphs of a character from the `GSUB` table.
+  ///
+  /// Alternates come from the single and alternate substitutions of the
+  /// `aalt`, `salt`, `swsh`, `cswh`, `titl`, `nalt`, `hist`, `ornm`, `ss01` to
+  /// `ss20` and `cv01` to `cv99` features, sorted by feature tag, then by the
+  /// feature value selecting them. Contextual substitutions are not followed.
+  ///
+  /// Returns an empty array when the codepoint is not mapped.
+  #[napi]
+  pub fn alternates_for(&self, codepoint: u32) -> Vec<GlyphAlternate> {
+    let face = self.face();
+    match char::from_u32(codepoint).and_then(|c| face.glyph_index(c)) {
+      Some(glyph) => substitution::alternates(&face, glyph),
+      None => Vec::new(),
+    }
+  }
+
+  /// Returns the ligature substitutions of the `GSUB` table, e.g. `f` `i`
+  /// to `fi`.
+  ///
+  /// Only the feature tagged `feature_tag` is considered when set, like
+  /// `liga`, `dlig`, `hlig`, `rlig` or a custom feature, and every feature
+  /// otherwise. Ligatures are sorted by feature tag, then components.
+  /// Contextual substitutions are not followed.
+  #[napi]
+  pub fn ligatures(&self, feature_tag: Option<String>) -> Vec<LigatureSubstitution> {
+    let feature_tag = match feature_tag {
+      Some(tag) => match variations::parse_tag(&tag) {
+        Some(tag) => Some(tag),
+        None => return Vec::new(),
+      },
+      None => None,
+    };
+
+    substitution::ligatures(&self.face(), feature_tag)
+  }
+
+  /// Outlines a glyph and returns its path commands and tight bounding box.
+  ///
+  /// Works with `glyf`, `CFF` and `CFF2` based faces.
+  ///
+  /// Returns `null` when the glyph has no outline (like a space) or when
+  /// the glyph id is out of range.
+  #[napi]
+  pub fn outline_glyph(&self, glyph_id: u16) -> Option<GlyphOutline> {
+    outline::outline_glyph(&self.face(), glyph_id)
+  }
+
+  /// Returns horizontal and vertical metrics and the bounding box of a glyph.
+  ///
+  /// This method is affected by variation axes.
+  ///
+  /// Returns `null` when the glyph id is out of range.
+  #[napi]
+  pub fn glyph_metrics(&self, glyph_id: u16) -> Option<GlyphMetrics> {
+    metrics::glyph_metrics(&self.face(), glyph_id)
+  }
+
+  /// Returns the metrics of every glyph as typed arrays indexed by glyph id.
+  ///
+  /// This method is affected by variation axes.
+  #[napi]
+  pub fn all_glyph_metrics(&self) -> GlyphMetricsArrays {
+    metrics::all_glyph_metrics(&self.face())
+  }
+
+  /// Returns the horizontal kerning between two glyphs in font units, or `0`
+  /// when the pair isn't kerned.
+  ///
+  /// Pair adjustments of the `kern` feature of the `GPOS` table are used,
+  /// including class-based ones. Fonts without one fall back to the legacy
+  /// `kern` table, then to the AAT `kerx` table. Only the advance of the left
+  /// glyph is adjusted and `GPOS` variation deltas are not applied.
+  #[napi]
+  pub fn kerning(&self, left_glyph: u16, right_glyph: u16) -> i16 {
+    kerning::kerning(&self.face(), GlyphId(left_glyph), GlyphId(right_glyph))
+  }
+
+  /// Returns every kerned pair of glyphs as parallel typed arrays, sorted by
+  /// left glyph id, then right glyph id.
+  ///
+  /// Values are the same as `kerning`, but class-based subtables of the
+  /// legacy `kern` and `kerx` tables are not listed.
+  #[napi]
+  pub fn kerning_pairs(&self) -> KerningPairs {
+    kerning::kerning_pairs(&self.face())
+  }
+
+  /// Measures `text` laid out on a single line at `font_size`.
+  ///
+  /// Characters are mapped through the `cmap` table, advances come from the
+  /// `hmtx` table and pair kerning from the legacy `kern` table. No shaping
+  /// is done, so ligatures and GPOS kerning are not taken into account.
+  ///
+  /// This method is affected by variation axes.
+  #[napi]
+  pub fn measure_text(
+    &self,
+    text: String,
+    font_size: f64,
+    options: Option<MeasureTextOptions>,
+  ) -> TextMeasurement {
+    text::measure_text(&self.face(), &text, font_size, options.unwrap_or_default())
+  }
+
+  /// Shapes `text` as a single run and returns positioned glyphs in visual
+  /// order.
+  ///
+  /// Runs a full OpenType shaping pipeline: characters are mapped through
+  /// the `cmap` table, substituted by the `GSUB` table and positioned by the
+  /// `GPOS` table (or the `kern` and `morx` tables when missing), for the
+  /// script, language and features in `options`.
+  ///
+  /// This method is affected by variation axes.
+  ///
+  /// Throws an error with a `code` of `InvalidShapeOption` when the script,
+  /// language, direction or one of the features cannot be parsed.
+  #[napi]
+  pub fn shape(
+    &self,
+    text: String,
+    options: Option<ShapeOptions>,
+  ) -> Result<Vec<ShapedGlyph>, CustomError> {
+    let glyphs = shaping::shape(self.face(), &text, options.unwrap_or_default())?;
+
+    Ok(glyphs)
+  }
+
+  /// Converts a glyph outline to SVG path data (the `d` attribute).
+  ///
+  /// Returns `null` when the glyph has no outline or when the glyph id is
+  /// out of range.
+  #[napi]
+  pub fn glyph_to_svg_path(
+    &self,
+    glyph_id: u16,
+    options: Option<SvgPathOptions>,
+  ) -> Option<String> {
+    svg::glyph_to_path(&self.face(), glyph_id, options.unwrap_or_default())
+  }
+
+  /// Renders `text` at the font `size` as a standalone `<svg>` document with
+  /// the glyphs converted to a single path.
+  ///
+  /// Glyphs are positioned with their horizontal advances only, without
+  /// kerning or shaping. Unmapped characters are drawn with the `.notdef` glyph.
+  #[napi]
+  pub fn text_to_svg(&self, text: String, size: f64, options: Option<SvgTextOptions>) -> String {
+    svg::text_to_svg(&self.face(), &text, size, options.unwrap_or_default())
+  }
+
+  /// Returns the extended metadata XML of a WOFF or WOFF2 file.
+  ///
+  /// Returns `null` when the font wasn't a web font or has no metadata.
+  #[napi(getter)]
+  pub fn woff_metadata(&self) -> Option<String> {
+    self.data.web_font()?.metadata.clone()
+  }
+
+  /// Returns the private data block of a WOFF or WOFF2 file.
+  ///
+  /// Returns `null` when the font wasn't a web font or has no private data.
+  #[napi(getter)]
+  pub fn woff_private_data(&self) -> Option<Buffer> {
+    self.data.web_font()?.private_data.clone().map(Buffer::from)
   }
 
   // #[wasm_bindgen(getter)]
@@ -0,0 +1,105 @@
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use ttf_parser::{Face, GlyphId};
+
+use crate::tables::Rect;
+
+/// Metrics of a single glyph, in font units.
+#[napi(object)]
+#[derive(Clone, Copy)]
+pub struct GlyphMetrics {
+  /// Horizontal advance from the `hmtx` table.
+  pub hor_advance: Option<u16>,
+
+  /// Left side bearing from the `hmtx` table.
+  pub left_side_bearing: Option<i16>,
+
+  /// Vertical advance from the `vmtx` table.
+  pub ver_advance: Option<u16>,
+
+  /// Top side bearing from the `vmtx` table.
+  pub top_side_bearing: Option<i16>,
+
+  /// Vertical origin from the `VORG` table.
+  pub vertical_origin: Option<i16>,
+
+  /// Bounding box of the glyph, `undefined` for glyphs without an outline.
+  pub bbox: Option<Rect>,
+}
+
+/// Metrics of all glyphs of a face, indexed by glyph id.
+///
+/// Values missing for a glyph are stored as `0`.
+#[napi(object)]
+pub struct GlyphMetricsArrays {
+  /// Horizontal advances from the `hmtx` table.
+  pub hor_advances: Uint16Array,
+
+  /// Left side bearings from the `hmtx` table.
+  pub left_side_bearings: Int16Array,
+
+  /// Vertical advances, `undefined` when the `vmtx` table is not present.
+  pub ver_advances: Option<Uint16Array>,
+
+  /// Top side bearings, `undefined` when the `vmtx` table is not present.
+  pub top_side_bearings: Option<Int16Array>,
+
+  /// Vertical origins, `undefined` when the `VORG` table is not present.
+  pub vertical_origins: Option<Int16Array>,
+
+  /// Bounding boxes as `xMin, yMin, xMax, yMax` quadruples.
+  pub bboxes: Int16Array,
+}
+
+/// Returns the metrics of a glyph, or `None` when the glyph id is out of range.
+pub fn glyph_metrics(face: &Face, glyph_id: u16) -> Option<GlyphMetrics> {
+  if glyph_id >= face.number_of_glyphs() {
+    return None;
+  }
+
+  let glyph_id = GlyphId(glyph_id);
+  Some(GlyphMetrics {
+    hor_advance: face.glyph_hor_advance(glyph_id),
+    left_side_bearing: face.glyph_hor_side_bearing(glyph_id),
+    ver_advance: face.glyph_ver_advance(glyph_id),
+    top_side_bearing: face.glyph_ver_side_bearing(glyph_id),
+    vertical_origin: face.glyph_y_origin(glyph_id),
+    bbox: face.glyph_bounding_box(glyph_id).map(Rect::from),
+  })
+}
+
+/// Returns the metrics of every glyph as typed arrays.
+pub fn all_glyph_metrics(face: &Face) -> GlyphMetricsArrays {
+  let count = face.number_of_glyphs() as usize;
+  let tables = face.tables();
+
+  let mut hor_advances = Vec::with_capacity(count);
+  let mut left_side_bearings = Vec::with_capacity(count);
+  let mut ver_advances = Vec::with_capacity(count);
+  let mut top_side_bearings = Vec::with_capacity(count);
+  let mut vertical_origins = Vec::with_capacity(count);
+  let mut bboxes = Vec::with_capacity(count * 4);
+
+  for id in 0..face.number_of_glyphs() {
+    let glyph_id = GlyphId(id);
+    hor_advances.push(face.glyph_hor_advance(glyph_id).unwrap_or(0));
+    left_side_bearings.push(face.glyph_hor_side_bearing(glyph_id).unwrap_or(0));
+    ver_advances.push(face.glyph_ver_advance(glyph_id).unwrap_or(0));
+    top_side_bearings.push(face.glyph_ver_side_bearing(glyph_id).unwrap_or(0));
+    vertical_origins.push(face.glyph_y_origin(glyph_id).unwrap_or(0));
+
+    match face.glyph_bounding_box(glyph_id) {
+      Some(bbox) => bboxes.extend([bbox.x_min, bbox.y_min, bbox.x_max, bbox.y_max]),
+      None => bboxes.extend([0; 4]),
+    }
+  }
+
+  GlyphMetricsArrays {
+    hor_advances: Uint16Array::new(hor_advances),
+    left_side_bearings: Int16Array::new(left_side_bearings),
+    ver_advances: tables.vmtx.map(|_| Uint16Array::new(ver_advances)),
+    top_side_bearings: tables.vmtx.map(|_| Int16Array::new(top_side_bearings)),
+    vertical_origins: tables.vorg.map(|_| Int16Array::new(vertical_origins)),
+    bboxes: Int16Array::new(bboxes),
+  }
+}
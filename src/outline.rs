@@ -0,0 +1,120 @@
+use napi_derive::napi;
+use ttf_parser::{Face, GlyphId, OutlineBuilder};
+
+use crate::tables::Rect;
+
+/// A kind of path command.
+#[napi]
+pub enum PathCommandKind {
+  /// Starts a new contour at `x`, `y`.
+  MoveTo,
+  /// Draws a line to `x`, `y`.
+  LineTo,
+  /// Draws a quadratic Bézier curve through `x1`, `y1` to `x`, `y`.
+  QuadTo,
+  /// Draws a cubic Bézier curve through `x1`, `y1` and `x2`, `y2` to `x`, `y`.
+  CurveTo,
+  /// Closes the current contour.
+  Close,
+}
+
+/// A single path command of a glyph outline, in font units.
+#[napi(object)]
+#[derive(Clone, Copy)]
+pub struct PathCommand {
+  pub kind: PathCommandKind,
+  pub x: Option<f64>,
+  pub y: Option<f64>,
+  pub x1: Option<f64>,
+  pub y1: Option<f64>,
+  pub x2: Option<f64>,
+  pub y2: Option<f64>,
+}
+
+impl PathCommand {
+  fn new(kind: PathCommandKind) -> Self {
+    Self {
+      kind,
+      x: None,
+      y: None,
+      x1: None,
+      y1: None,
+      x2: None,
+      y2: None,
+    }
+  }
+
+  fn to(self, x: f32, y: f32) -> Self {
+    Self {
+      x: Some(x.into()),
+      y: Some(y.into()),
+      ..self
+    }
+  }
+}
+
+/// An outline of a glyph.
+#[napi(object)]
+#[derive(Clone)]
+pub struct GlyphOutline {
+  /// Path commands describing the outline.
+  pub commands: Vec<PathCommand>,
+
+  /// A tight bounding box of the outline.
+  pub bbox: Rect,
+}
+
+/// Collects the calls of a [`OutlineBuilder`] into [`PathCommand`]s.
+#[derive(Default)]
+struct Collector {
+  commands: Vec<PathCommand>,
+}
+
+impl OutlineBuilder for Collector {
+  fn move_to(&mut self, x: f32, y: f32) {
+    self
+      .commands
+      .push(PathCommand::new(PathCommandKind::MoveTo).to(x, y));
+  }
+
+  fn line_to(&mut self, x: f32, y: f32) {
+    self
+      .commands
+      .push(PathCommand::new(PathCommandKind::LineTo).to(x, y));
+  }
+
+  fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+    self.commands.push(PathCommand {
+      x1: Some(x1.into()),
+      y1: Some(y1.into()),
+      ..PathCommand::new(PathCommandKind::QuadTo).to(x, y)
+    });
+  }
+
+  fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+    self.commands.push(PathCommand {
+      x1: Some(x1.into()),
+      y1: Some(y1.into()),
+      x2: Some(x2.into()),
+      y2: Some(y2.into()),
+      ..PathCommand::new(PathCommandKind::CurveTo).to(x, y)
+    });
+  }
+
+  fn close(&mut self) {
+    self.commands.push(PathCommand::new(PathCommandKind::Close));
+  }
+}
+
+/// Outlines a glyph from the `glyf`, `CFF` or `CFF2` table.
+///
+/// Returns `None` when the glyph has no outline, e.g. a space.
+pub fn outline_glyph(face: &Face, glyph_id: u16) -> Option<GlyphOutline> {
+  let mut collector = Collector::default();
+  let bbox = face.outline_glyph(GlyphId(glyph_id), &mut collector)?;
+
+  Some(GlyphOutline {
+    commands: collector.commands,
+    bbox: bbox.into(),
+  })
+}
@@ -0,0 +1,119 @@
+use std::str::FromStr;
+
+use napi_derive::napi;
+use rustybuzz::{Direction, Feature, Language, Script, UnicodeBuffer};
+use ttf_parser::Face;
+
+use crate::error::CustomError;
+
+/// Options of `shape`.
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct ShapeOptions {
+  /// ISO 15924 script tag, e.g. `Latn` or `Arab`. Guessed from the text when
+  /// not set.
+  pub script: Option<String>,
+
+  /// BCP 47 language tag, e.g. `en` or `tr`, used to select a language
+  /// system. The default language system is used when not set.
+  pub language: Option<String>,
+
+  /// Text direction, one of `ltr`, `rtl`, `ttb` or `btt`. Guessed from the
+  /// script when not set.
+  pub direction: Option<String>,
+
+  /// Features to enable or disable in HarfBuzz syntax, e.g.
+  /// `['-liga', 'ss01', 'kern=0']`. The default features of the script are
+  /// applied as well.
+  pub features: Option<Vec<String>>,
+}
+
+/// A positioned glyph, in font units.
+#[napi(object)]
+#[derive(Clone, Copy)]
+pub struct ShapedGlyph {
+  /// The glyph id.
+  pub glyph_id: u16,
+
+  /// Index of the first UTF-16 code unit of the characters the glyph was
+  /// shaped from.
+  pub cluster: u32,
+
+  /// How much the line advances horizontally after drawing this glyph.
+  pub x_advance: i32,
+
+  /// How much the line advances vertically after drawing this glyph.
+  pub y_advance: i32,
+
+  /// How much the glyph moves horizontally before drawing it.
+  pub x_offset: i32,
+
+  /// How much the glyph moves vertically before drawing it.
+  pub y_offset: i32,
+}
+
+/// Shapes `text` with `face`, at the variation coordinates set on it.
+pub fn shape(
+  face: Face,
+  text: &str,
+  options: ShapeOptions,
+) -> Result<Vec<ShapedGlyph>, CustomError> {
+  let face = rustybuzz::Face::from_face(face);
+
+  let mut buffer = UnicodeBuffer::new();
+  buffer.push_str(text);
+  if let Some(script) = options.script {
+    // `Script` accepts any tag, so reject what cannot be an ISO 15924 code.
+    if script.len() != 4 || !script.bytes().all(|b| b.is_ascii_alphabetic()) {
+      return Err(invalid_option("script", &script));
+    }
+    buffer.set_script(parse_option::<Script>("script", &script)?);
+  }
+  if let Some(language) = options.language {
+    buffer.set_language(parse_option::<Language>("language", &language)?);
+  }
+  if let Some(direction) = options.direction {
+    buffer.set_direction(parse_option::<Direction>("direction", &direction)?);
+  }
+  let features = options
+    .features
+    .unwrap_or_default()
+    .iter()
+    .map(|feature| parse_option::<Feature>("feature", feature))
+    .collect::<Result<Vec<_>, _>>()?;
+
+  let glyphs = rustybuzz::shape(&face, &features, buffer);
+
+  // Clusters are UTF-8 byte offsets, while JS strings are indexed by UTF-16 code units.
+  let mut utf16_offsets = vec![0; text.len() + 1];
+  let mut utf16_offset = 0;
+  for (byte_offset, c) in text.char_indices() {
+    utf16_offsets[byte_offset] = utf16_offset;
+    utf16_offset += c.len_utf16() as u32;
+  }
+  utf16_offsets[text.len()] = utf16_offset;
+
+  Ok(
+    glyphs
+      .glyph_infos()
+      .iter()
+      .zip(glyphs.glyph_positions())
+      .map(|(info, position)| ShapedGlyph {
+        glyph_id: info.glyph_id as u16,
+        cluster: utf16_offsets[info.cluster as usize],
+        x_advance: position.x_advance,
+        y_advance: position.y_advance,
+        x_offset: position.x_offset,
+        y_offset: position.y_offset,
+      })
+      .collect(),
+  )
+}
+
+fn parse_option<T: FromStr>(name: &str, value: &str) -> Result<T, CustomError> {
+  value.parse().map_err(|_| invalid_option(name, value))
+}
+
+fn invalid_option(name: &str, value: &str) -> CustomError {
+  CustomError::InvalidShapeOption(format!("{} `{}`", name, value))
+}
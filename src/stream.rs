@@ -0,0 +1,63 @@
+/// A minimal big-endian reader for the bits of font data `ttf_parser` doesn't expose.
+#[derive(Clone, Copy)]
+pub struct Stream<'a> {
+  data: &'a [u8],
+  offset: usize,
+}
+
+impl<'a> Stream<'a> {
+  pub fn new(data: &'a [u8]) -> Self {
+    Self { data, offset: 0 }
+  }
+
+  /// Creates a stream positioned at `offset`, or `None` when out of bounds.
+  pub fn new_at(data: &'a [u8], offset: usize) -> Option<Self> {
+    if offset <= data.len() {
+      Some(Self { data, offset })
+    } else {
+      None
+    }
+  }
+
+  pub fn offset(&self) -> usize {
+    self.offset
+  }
+
+  pub fn skip(&mut self, len: usize) -> Option<()> {
+    self.read_bytes(len).map(|_| ())
+  }
+
+  pub fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+    let end = self.offset.checked_add(len)?;
+    let bytes = self.data.get(self.offset..end)?;
+    self.offset = end;
+    Some(bytes)
+  }
+
+  fn read_array<const N: usize>(&mut self) -> Option<[u8; N]> {
+    self.read_bytes(N)?.try_into().ok()
+  }
+
+  pub fn read_u8(&mut self) -> Option<u8> {
+    self.read_array::<1>().map(|b| b[0])
+  }
+
+  pub fn read_u16(&mut self) -> Option<u16> {
+    self.read_array().map(u16::from_be_bytes)
+  }
+
+  pub fn read_i16(&mut self) -> Option<i16> {
+    self.read_array().map(i16::from_be_bytes)
+  }
+
+  pub fn read_u32(&mut self) -> Option<u32> {
+    self.read_array().map(u32::from_be_bytes)
+  }
+
+  /// Reads a 16.16 fixed-point number.
+  pub fn read_fixed(&mut self) -> Option<f64> {
+    self
+      .read_array()
+      .map(|b| f64::from(i32::from_be_bytes(b)) / 65536.0)
+  }
+}
@@ -0,0 +1,214 @@
+use napi_derive::napi;
+use ttf_parser::gsub::{SingleSubstitution, SubstitutionSubtable};
+use ttf_parser::{Face, GlyphId, Tag};
+
+use crate::tables::cmap::glyph_to_codepoints;
+use crate::tables::layout::{coverage_glyphs, Feature, LayoutTable};
+
+/// An alternate glyph of a character.
+#[napi(object)]
+#[derive(Clone)]
+pub struct GlyphAlternate {
+  /// The alternate glyph id.
+  pub glyph_id: u16,
+
+  /// Tag of the feature producing the alternate, e.g. `salt` or `ss01`.
+  pub feature_tag: String,
+
+  /// Feature value selecting the alternate, e.g. `2` for `salt=2`.
+  pub feature_value: u32,
+
+  /// UI name of a stylistic set or character variant feature, `undefined`
+  /// when the font doesn't name the feature.
+  pub feature_name: Option<String>,
+}
+
+/// A ligature substitution.
+#[napi(object)]
+#[derive(Clone)]
+pub struct LigatureSubstitution {
+  /// Tag of the feature applying the substitution, e.g. `liga`.
+  pub feature_tag: String,
+
+  /// Glyph ids of the components, in logical order.
+  pub components: Vec<u16>,
+
+  /// Glyph id of the ligature.
+  pub glyph_id: u16,
+
+  /// The characters the components are mapped from in the `cmap` table,
+  /// e.g. `=>`, `undefined` when a component isn't mapped from any character.
+  pub text: Option<String>,
+}
+
+/// Checks that a feature substitutes glyphs with stylistic alternates.
+fn is_alternate_feature(tag: Tag) -> bool {
+  let bytes = tag.to_bytes();
+  let is_numbered =
+    |prefix: &[u8]| bytes.starts_with(prefix) && bytes[2..].iter().all(u8::is_ascii_digit);
+
+  matches!(
+    &bytes,
+    b"aalt" | b"salt" | b"swsh" | b"cswh" | b"titl" | b"nalt" | b"hist" | b"ornm"
+  ) || is_numbered(b"ss")
+    || is_numbered(b"cv")
+}
+
+/// Returns the alternates of `glyph` produced by the single and alternate
+/// substitutions of the alternate features of the `GSUB` table, sorted by
+/// feature tag, then feature value.
+pub fn alternates(face: &Face, glyph: GlyphId) -> Vec<GlyphAlternate> {
+  let Some(gsub) = face.tables().gsub else {
+    return Vec::new();
+  };
+  // Used for the UI names of the features only.
+  let layout = LayoutTable::new(face, Tag::from_bytes(b"GSUB"), Some(gsub));
+
+  let mut alternates = Vec::new();
+  for (index, feature) in gsub.features.into_iter().enumerate() {
+    if !is_alternate_feature(feature.tag) {
+      continue;
+    }
+
+    let feature_name = layout
+      .as_ref()
+      .and_then(|layout| layout.features.get(index))
+      .and_then(feature_name);
+    let mut push = |glyph_id: GlyphId, feature_value: u32| {
+      alternates.push(GlyphAlternate {
+        glyph_id: glyph_id.0,
+        feature_tag: feature.tag.to_string(),
+        feature_value,
+        feature_name: feature_name.clone(),
+      })
+    };
+
+    for lookup in feature
+      .lookup_indices
+      .into_iter()
+      .filter_map(|index| gsub.lookups.get(index))
+    {
+      // Only the first subtable covering the glyph is applied.
+      for subtable in lookup.subtables.into_iter::<SubstitutionSubtable>() {
+        match subtable {
+          SubstitutionSubtable::Single(single) => {
+            if let Some(substitute) = single_substitute(&single, glyph) {
+              push(substitute, 1);
+              break;
+            }
+          }
+          SubstitutionSubtable::Alternate(alternate) => {
+            if let Some(set) = alternate
+              .coverage
+              .get(glyph)
+              .and_then(|index| alternate.alternate_sets.get(index))
+            {
+              // Feature values select alternates starting from `1`.
+              for (substitute, value) in set.alternates.into_iter().zip(1..) {
+                push(substitute, value);
+              }
+              break;
+            }
+          }
+          _ => {}
+        }
+      }
+    }
+  }
+
+  alternates.retain(|alternate| alternate.glyph_id != glyph.0);
+  // The same feature can be listed once per script.
+  let key = |a: &GlyphAlternate| (a.feature_tag.clone(), a.feature_value, a.glyph_id);
+  alternates.sort_by_key(key);
+  alternates.dedup_by_key(|a| key(a));
+  alternates
+}
+
+/// Returns the UI name of a stylistic set or character variant feature.
+fn feature_name(feature: &Feature) -> Option<String> {
+  match (&feature.stylistic_set, &feature.character_variant) {
+    (Some(params), _) => params.ui_name.clone(),
+    (_, Some(params)) => params.ui_label.clone(),
+    _ => None,
+  }
+}
+
+fn single_substitute(subtable: &SingleSubstitution, glyph: GlyphId) -> Option<GlyphId> {
+  match subtable {
+    SingleSubstitution::Format1 { coverage, delta } => {
+      coverage.get(glyph)?;
+      // Deltas wrap around modulo 65536.
+      Some(GlyphId((i32::from(glyph.0) + i32::from(*delta)) as u16))
+    }
+    SingleSubstitution::Format2 {
+      coverage,
+      substitutes,
+    } => substitutes.get(coverage.get(glyph)?),
+  }
+}
+
+/// Returns the ligature substitutions of the features of the `GSUB` table
+/// tagged `feature_tag`, or of every feature when `None`, sorted by feature
+/// tag, then components.
+pub fn ligatures(face: &Face, feature_tag: Option<Tag>) -> Vec<LigatureSubstitution> {
+  let Some(gsub) = face.tables().gsub else {
+    return Vec::new();
+  };
+  let codepoints = glyph_to_codepoints(face);
+  // Components mapped from several characters use the lowest one.
+  let text = |components: &[u16]| {
+    components
+      .iter()
+      .map(|&glyph| {
+        let codepoint = codepoints.get(usize::from(glyph))?.first()?;
+        char::from_u32(*codepoint)
+      })
+      .collect::<Option<String>>()
+  };
+
+  let mut ligatures = Vec::new();
+  for feature in gsub.features {
+    if feature_tag.is_some_and(|tag| tag != feature.tag) {
+      continue;
+    }
+
+    for lookup in feature
+      .lookup_indices
+      .into_iter()
+      .filter_map(|index| gsub.lookups.get(index))
+    {
+      for subtable in lookup.subtables.into_iter::<SubstitutionSubtable>() {
+        let SubstitutionSubtable::Ligature(subtable) = subtable else {
+          continue;
+        };
+
+        // Ligature sets are in coverage order, one per first component.
+        for (first, set) in coverage_glyphs(subtable.coverage, face.number_of_glyphs())
+          .into_iter()
+          .filter_map(|first| {
+            let index = subtable.coverage.get(first)?;
+            Some((first, subtable.ligature_sets.get(index)?))
+          })
+        {
+          for ligature in set {
+            let components: Vec<u16> = std::iter::once(first.0)
+              .chain(ligature.components.into_iter().map(|GlyphId(id)| id))
+              .collect();
+            ligatures.push(LigatureSubstitution {
+              feature_tag: feature.tag.to_string(),
+              text: text(&components),
+              components,
+              glyph_id: ligature.glyph.0,
+            });
+          }
+        }
+      }
+    }
+  }
+
+  // The same feature can be listed once per script.
+  let key = |l: &LigatureSubstitution| (l.feature_tag.clone(), l.components.clone(), l.glyph_id);
+  ligatures.sort_by_key(key);
+  ligatures.dedup_by_key(|l| key(l));
+  ligatures
+}
@@ -0,0 +1,197 @@
+use std::fmt::Write;
+
+use napi_derive::napi;
+use ttf_parser::{Face, GlyphId, OutlineBuilder};
+
+const DEFAULT_PRECISION: u32 = 2;
+
+/// Larger precisions only add noise to `f64` coordinates.
+const MAX_PRECISION: u32 = 20;
+
+/// Options of `glyphToSvgPath`.
+#[napi(object)]
+#[derive(Clone, Copy, Default)]
+pub struct SvgPathOptions {
+  /// Number of decimal places of the coordinates, up to 20. Defaults to 2.
+  pub precision: Option<u32>,
+
+  /// Flips the y-axis so the path can be drawn in SVG's y-down coordinate
+  /// system, with the baseline at `y = 0`. Defaults to `true`.
+  pub flip_y: Option<bool>,
+
+  /// Font size to scale the path to. Font units are used when not set.
+  pub size: Option<f64>,
+}
+
+/// Options of `textToSvg`.
+#[napi(object)]
+#[derive(Clone, Default)]
+pub struct SvgTextOptions {
+  /// Number of decimal places of the coordinates, up to 20. Defaults to 2.
+  pub precision: Option<u32>,
+
+  /// Fill of the text path. Defaults to `black`.
+  pub fill: Option<String>,
+}
+
+/// Maps font units to SVG user units.
+#[derive(Clone, Copy)]
+struct Transform {
+  scale: f64,
+  dx: f64,
+  dy: f64,
+  flip_y: bool,
+}
+
+impl Transform {
+  fn apply(&self, x: f32, y: f32) -> (f64, f64) {
+    let x = self.dx + f64::from(x) * self.scale;
+    let y = f64::from(y) * self.scale;
+    let y = if self.flip_y {
+      self.dy - y
+    } else {
+      self.dy + y
+    };
+    (x, y)
+  }
+}
+
+/// Writes outline calls as SVG path data.
+struct SvgBuilder {
+  d: String,
+  precision: usize,
+  transform: Transform,
+}
+
+impl SvgBuilder {
+  fn point(&mut self, x: f32, y: f32) {
+    let (x, y) = self.transform.apply(x, y);
+    let x = format_number(x, self.precision);
+    let y = format_number(y, self.precision);
+    write!(self.d, "{} {}", x, y).unwrap();
+  }
+}
+
+impl OutlineBuilder for SvgBuilder {
+  fn move_to(&mut self, x: f32, y: f32) {
+    self.d.push('M');
+    self.point(x, y);
+  }
+
+  fn line_to(&mut self, x: f32, y: f32) {
+    self.d.push('L');
+    self.point(x, y);
+  }
+
+  fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+    self.d.push('Q');
+    self.point(x1, y1);
+    self.d.push(' ');
+    self.point(x, y);
+  }
+
+  fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+    self.d.push('C');
+    self.point(x1, y1);
+    self.d.push(' ');
+    self.point(x2, y2);
+    self.d.push(' ');
+    self.point(x, y);
+  }
+
+  fn close(&mut self) {
+    self.d.push('Z');
+  }
+}
+
+/// Returns the number of decimal places to format coordinates with.
+fn precision(precision: Option<u32>) -> usize {
+  precision.unwrap_or(DEFAULT_PRECISION).min(MAX_PRECISION) as usize
+}
+
+/// Formats `value` with at most `precision` decimal places and no trailing zeros.
+fn format_number(value: f64, precision: usize) -> String {
+  let formatted = format!("{:.*}", precision, value);
+  let trimmed = if formatted.contains('.') {
+    formatted.trim_end_matches('0').trim_end_matches('.')
+  } else {
+    &formatted
+  };
+
+  match trimmed {
+    "-0" => "0".to_owned(),
+    _ => trimmed.to_owned(),
+  }
+}
+
+/// Converts a glyph outline to SVG path data.
+///
+/// Returns `None` when the glyph has no outline.
+pub fn glyph_to_path(face: &Face, glyph_id: u16, options: SvgPathOptions) -> Option<String> {
+  let scale = options
+    .size
+    .map_or(1.0, |size| size / f64::from(face.units_per_em()));
+
+  let mut builder = SvgBuilder {
+    d: String::new(),
+    precision: precision(options.precision),
+    transform: Transform {
+      scale,
+      dx: 0.0,
+      dy: 0.0,
+      flip_y: options.flip_y.unwrap_or(true),
+    },
+  };
+  face.outline_glyph(GlyphId(glyph_id), &mut builder)?;
+
+  Some(builder.d)
+}
+
+/// Lays out `text` on a single line using horizontal advances and renders it
+/// as a standalone SVG document.
+///
+/// Unmapped characters are drawn with the `.notdef` glyph.
+pub fn text_to_svg(face: &Face, text: &str, size: f64, options: SvgTextOptions) -> String {
+  let scale = size / f64::from(face.units_per_em());
+  let ascender = f64::from(face.ascender()) * scale;
+  let height = (f64::from(face.ascender()) - f64::from(face.descender())) * scale;
+  let precision = precision(options.precision);
+
+  let mut builder = SvgBuilder {
+    d: String::new(),
+    precision,
+    transform: Transform {
+      scale,
+      dx: 0.0,
+      dy: ascender,
+      flip_y: true,
+    },
+  };
+
+  for c in text.chars() {
+    let glyph_id = face.glyph_index(c).unwrap_or(GlyphId(0));
+    face.outline_glyph(glyph_id, &mut builder);
+
+    let advance = face.glyph_hor_advance(glyph_id).unwrap_or(0);
+    builder.transform.dx += f64::from(advance) * scale;
+  }
+
+  let width = format_number(builder.transform.dx, precision);
+  let height = format_number(height, precision);
+  let fill = options.fill.as_deref().unwrap_or("black");
+
+  format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\"><path d=\"{d}\" fill=\"{fill}\"/></svg>",
+    w = width,
+    h = height,
+    d = builder.d,
+    fill = escape_attribute(fill),
+  )
+}
+
+fn escape_attribute(value: &str) -> String {
+  value
+    .replace('&', "&amp;")
+    .replace('"', "&quot;")
+    .replace('<', "&lt;")
+}
@@ -0,0 +1,248 @@
+use std::collections::HashSet;
+
+use crate::stream::Stream;
+use crate::tables::name::PlatformId;
+use napi_derive::napi;
+use ttf_parser::{cmap, Face, Tag};
+
+/// A [character encoding subtable](https://docs.microsoft.com/en-us/typography/opentype/spec/cmap#encoding-records-and-encodings).
+#[napi(object)]
+#[derive(Clone)]
+pub struct Subtable {
+  /// Subtable platform.
+  pub platform_id: PlatformId,
+
+  /// Subtable encoding.
+  pub encoding_id: u16,
+
+  /// Subtable format, e.g. `4` or `12`.
+  pub format: u16,
+
+  /// Checks that the current encoding is Unicode compatible.
+  pub is_unicode: bool,
+}
+
+impl From<cmap::Subtable<'_>> for Subtable {
+  fn from(subtable: cmap::Subtable) -> Subtable {
+    let format = match subtable.format {
+      cmap::Format::ByteEncodingTable(_) => 0,
+      cmap::Format::HighByteMappingThroughTable(_) => 2,
+      cmap::Format::SegmentMappingToDeltaValues(_) => 4,
+      cmap::Format::TrimmedTableMapping(_) => 6,
+      cmap::Format::MixedCoverage => 8,
+      cmap::Format::TrimmedArray(_) => 10,
+      cmap::Format::SegmentedCoverage(_) => 12,
+      cmap::Format::ManyToOneRangeMappings(_) => 13,
+      cmap::Format::UnicodeVariationSequences(_) => 14,
+    };
+
+    Subtable {
+      platform_id: subtable.platform_id.into(),
+      encoding_id: subtable.encoding_id,
+      format,
+      is_unicode: subtable.is_unicode(),
+    }
+  }
+}
+
+/// A [Character to Glyph Index Mapping Table](https://docs.microsoft.com/en-us/typography/opentype/spec/cmap).
+#[napi(js_name = "CMAPTable", object)]
+#[derive(Clone)]
+pub struct Table {
+  /// A list of subtables.
+  pub subtables: Vec<Subtable>,
+}
+
+impl Table {
+  pub fn new(table: Option<cmap::Table>) -> Option<Self> {
+    let table = table?;
+
+    Some(Self {
+      subtables: subtables(table.subtables)
+        .map(|(_, subtable)| Subtable::from(subtable))
+        .collect(),
+    })
+  }
+}
+
+/// Iterates over the subtables along with their encoding record index.
+///
+/// Unlike `Subtables::into_iter`, which stops at the first subtable that
+/// cannot be parsed, only the failing subtables are skipped.
+fn subtables(subtables: cmap::Subtables) -> impl Iterator<Item = (usize, cmap::Subtable)> {
+  (0..subtables.len()).filter_map(move |index| Some((usize::from(index), subtables.get(index)?)))
+}
+
+/// An inclusive range of Unicode codepoints.
+#[napi(object)]
+#[derive(Clone, Copy)]
+pub struct CodepointRange {
+  /// First codepoint of the range.
+  pub start: u32,
+
+  /// Last codepoint of the range.
+  pub end: u32,
+}
+
+/// Calls `f` with every codepoint and glyph pair of the Unicode subtables.
+///
+/// A codepoint is reported once per subtable that maps it. Encoding records
+/// sharing a subtable only report it once.
+fn for_each_mapping(face: &Face, mut f: impl FnMut(u32, u16)) {
+  let Some(table) = face.tables().cmap else {
+    return;
+  };
+  let data = face
+    .raw_face()
+    .table(Tag::from_bytes(b"cmap"))
+    .unwrap_or_default();
+
+  let mut offsets = HashSet::new();
+  // Subtables are iterated in encoding record order.
+  for (index, subtable) in subtables(table.subtables) {
+    if !subtable.is_unicode() || !offsets.insert(subtable_offset(data, index)) {
+      continue;
+    }
+
+    // Subtables may still map listed codepoints to `.notdef`.
+    let mut map = |codepoint: u32| match subtable.glyph_index(codepoint) {
+      Some(glyph) if glyph.0 != 0 && codepoint <= char::MAX as u32 => f(codepoint, glyph.0),
+      _ => {}
+    };
+
+    match subtable.format {
+      cmap::Format::SegmentMappingToDeltaValues(_)
+      | cmap::Format::SegmentedCoverage(_)
+      | cmap::Format::ManyToOneRangeMappings(_) => {
+        for (start, end) in segment_ranges(data, index).unwrap_or_default() {
+          (start..=end).for_each(&mut map);
+        }
+      }
+      _ => subtable.codepoints(map),
+    }
+  }
+}
+
+/// Returns the offset of the subtable of the encoding record at `index`.
+fn subtable_offset(data: &[u8], index: usize) -> Option<u32> {
+  let mut s = Stream::new_at(data, 4 + index * 8)?;
+  s.skip(4)?; // platformID, encodingID
+  s.read_u32()
+}
+
+/// Returns the codepoints covered by the segments of a format 4 subtable or
+/// the groups of a format 12 or 13 subtable, as sorted, non-overlapping
+/// ranges up to `char::MAX`.
+///
+/// Segments can overlap each other and groups can cover up to `0xFFFFFFFF`,
+/// so they are not walked directly.
+fn segment_ranges(data: &[u8], index: usize) -> Option<Vec<(u32, u32)>> {
+  let subtable = data.get(subtable_offset(data, index)? as usize..)?;
+  let mut s = Stream::new(subtable);
+  let mut segments = Vec::new();
+  match s.read_u16()? {
+    4 => {
+      s.skip(4)?; // length, language
+      let seg_count = usize::from(s.read_u16()? / 2);
+      s.skip(6)?; // searchRange, entrySelector, rangeShift
+      let end_codes = s.read_bytes(seg_count * 2)?;
+      s.skip(2)?; // reservedPad
+      let start_codes = s.read_bytes(seg_count * 2)?;
+
+      let read = |codes: &[u8]| u16::from_be_bytes([codes[0], codes[1]]);
+      for (start, end) in start_codes
+        .chunks(2)
+        .map(read)
+        .zip(end_codes.chunks(2).map(read))
+      {
+        // The last segment maps `0xFFFF` and ends the table.
+        if start == 0xFFFF && end == 0xFFFF {
+          break;
+        }
+        segments.push((u32::from(start), u32::from(end)));
+      }
+    }
+    12 | 13 => {
+      s.skip(10)?; // reserved, length, language
+      let count = s.read_u32()?;
+      for _ in 0..count {
+        let start = s.read_u32()?;
+        let end = s.read_u32()?.min(char::MAX as u32);
+        s.skip(4)?; // startGlyphID
+        segments.push((start, end));
+      }
+    }
+    _ => return None,
+  }
+
+  segments.sort_unstable();
+  let mut ranges: Vec<(u32, u32)> = Vec::new();
+  for (start, end) in segments.into_iter().filter(|(start, end)| start <= end) {
+    match ranges.last_mut() {
+      Some(range) if start <= range.1.saturating_add(1) => range.1 = range.1.max(end),
+      _ => ranges.push((start, end)),
+    }
+  }
+  Some(ranges)
+}
+
+/// Collects every codepoint mapped to a glyph by a Unicode subtable, merged
+/// into sorted, non-overlapping ranges.
+pub fn codepoint_ranges(face: &Face) -> Vec<CodepointRange> {
+  // One bit per Unicode scalar value.
+  let mut is_mapped = vec![0u64; (char::MAX as usize + 1).div_ceil(64)];
+  for_each_mapping(face, |codepoint, _| {
+    is_mapped[codepoint as usize / 64] |= 1 << (codepoint % 64);
+  });
+
+  let mut ranges: Vec<CodepointRange> = Vec::new();
+  for (i, &word) in is_mapped.iter().enumerate() {
+    if word == 0 {
+      continue;
+    }
+
+    for bit in (0..64).filter(|bit| word & (1 << bit) != 0) {
+      let codepoint = (i * 64 + bit) as u32;
+      match ranges.last_mut() {
+        Some(range) if range.end + 1 == codepoint => range.end = codepoint,
+        _ => ranges.push(CodepointRange {
+          start: codepoint,
+          end: codepoint,
+        }),
+      }
+    }
+  }
+
+  ranges
+}
+
+/// Returns the sorted codepoints mapped to `glyph_id` by the Unicode subtables.
+pub fn codepoints_for_glyph(face: &Face, glyph_id: u16) -> Vec<u32> {
+  let mut codepoints = Vec::new();
+  for_each_mapping(face, |codepoint, glyph| {
+    if glyph == glyph_id {
+      codepoints.push(codepoint);
+    }
+  });
+
+  codepoints.sort_unstable();
+  codepoints.dedup();
+  codepoints
+}
+
+/// Builds a glyph id to codepoints map from the Unicode subtables, indexed by
+/// glyph id, with one entry per glyph of the face.
+pub fn glyph_to_codepoints(face: &Face) -> Vec<Vec<u32>> {
+  let mut map = vec![Vec::new(); face.number_of_glyphs() as usize];
+  for_each_mapping(face, |codepoint, glyph| {
+    if let Some(codepoints) = map.get_mut(glyph as usize) {
+      codepoints.push(codepoint);
+    }
+  });
+
+  for codepoints in &mut map {
+    codepoints.sort_unstable();
+    codepoints.dedup();
+  }
+  map
+}
@@ -0,0 +1,228 @@
+use crate::stream::Stream;
+use napi_derive::napi;
+use ttf_parser::{name, Face, Tag};
+
+use super::name::find_name;
+
+/// The axis should not be exposed directly in user interfaces.
+const HIDDEN_AXIS: u16 = 0x0001;
+
+/// A [variation axis](https://docs.microsoft.com/en-us/typography/opentype/spec/fvar#variationaxisrecord).
+#[napi(object)]
+#[derive(Clone)]
+pub struct VariationAxis {
+  /// Axis tag, e.g. `wght`.
+  pub tag: String,
+
+  /// Minimum coordinate value.
+  pub min_value: f64,
+
+  /// Default coordinate value.
+  pub default_value: f64,
+
+  /// Maximum coordinate value.
+  pub max_value: f64,
+
+  /// Checks that the axis should not be exposed in user interfaces.
+  pub hidden: bool,
+
+  /// Axis name ID in the `name` table.
+  pub name_id: u16,
+
+  /// Axis name, `undefined` when not present in the `name` table.
+  pub name: Option<String>,
+}
+
+/// A [named instance](https://docs.microsoft.com/en-us/typography/opentype/spec/fvar#instancerecord).
+#[napi(object)]
+#[derive(Clone)]
+pub struct NamedInstance {
+  /// Coordinates of the instance, in the order of the axes.
+  pub coordinates: Vec<f64>,
+
+  /// Subfamily name ID in the `name` table.
+  pub subfamily_name_id: u16,
+
+  /// Subfamily name, e.g. `Bold`, `undefined` when not present in the `name` table.
+  pub subfamily_name: Option<String>,
+
+  /// PostScript name ID in the `name` table, `undefined` when the instance has none.
+  pub post_script_name_id: Option<u16>,
+
+  /// PostScript name, `undefined` when the instance has none or when it is
+  /// not present in the `name` table.
+  pub post_script_name: Option<String>,
+}
+
+/// A [Font Variations Table](https://docs.microsoft.com/en-us/typography/opentype/spec/fvar).
+#[napi(js_name = "FVARTable", object)]
+#[derive(Clone)]
+pub struct Table {
+  /// A list of variation axes.
+  pub axes: Vec<VariationAxis>,
+
+  /// A list of named instances.
+  pub instances: Vec<NamedInstance>,
+}
+
+impl Table {
+  pub fn new(face: &Face) -> Option<Self> {
+    // Make sure `ttf_parser` considers the table valid before reading it.
+    face.tables().fvar?;
+    let data = face.raw_face().table(Tag::from_bytes(b"fvar"))?;
+    Self::parse(data, face.tables().name)
+  }
+
+  fn parse(data: &[u8], names: Option<name::Table>) -> Option<Self> {
+    let mut s = Stream::new(data);
+    s.skip(4)?; // majorVersion, minorVersion
+    let axes_array_offset = s.read_u16()?;
+    s.skip(2)?; // reserved
+    let axis_count = s.read_u16()?;
+    let axis_size = s.read_u16()?;
+    let instance_count = s.read_u16()?;
+    let instance_size = s.read_u16()?;
+
+    let mut s = Stream::new_at(data, axes_array_offset.into())?;
+    let mut axes = Vec::with_capacity(axis_count.into());
+    for _ in 0..axis_count {
+      let mut record = Stream::new(s.read_bytes(axis_size.into())?);
+      axes.push(parse_axis(&mut record, names)?);
+    }
+
+    // The `postScriptNameID` field is optional and only present when the
+    // record is large enough to hold it.
+    let coordinates_size = usize::from(axis_count) * 4;
+    let has_post_script_name = usize::from(instance_size) >= coordinates_size + 6;
+
+    let mut instances = Vec::with_capacity(instance_count.into());
+    for _ in 0..instance_count {
+      let mut record = Stream::new(s.read_bytes(instance_size.into())?);
+      let subfamily_name_id = record.read_u16()?;
+      record.skip(2)?; // flags
+      let coordinates = (0..axis_count)
+        .map(|_| record.read_fixed())
+        .collect::<Option<Vec<_>>>()?;
+      let post_script_name_id = match has_post_script_name {
+        true => record.read_u16().filter(|&id| id != 0xFFFF),
+        false => None,
+      };
+
+      instances.push(NamedInstance {
+        coordinates,
+        subfamily_name_id,
+        subfamily_name: find_name(names, subfamily_name_id),
+        post_script_name_id,
+        post_script_name: post_script_name_id.and_then(|id| find_name(names, id)),
+      });
+    }
+
+    Some(Self { axes, instances })
+  }
+}
+
+fn parse_axis(s: &mut Stream, names: Option<name::Table>) -> Option<VariationAxis> {
+  let tag = Tag::from_bytes(s.read_bytes(4)?.try_into().ok()?);
+  let min_value = s.read_fixed()?;
+  let default_value = s.read_fixed()?;
+  let max_value = s.read_fixed()?;
+  let flags = s.read_u16()?;
+  let name_id = s.read_u16()?;
+
+  Some(VariationAxis {
+    tag: tag.to_string(),
+    min_value: min_value.min(default_value),
+    default_value,
+    max_value: max_value.max(default_value),
+    hidden: flags & HIDDEN_AXIS != 0,
+    name_id,
+    name: find_name(names, name_id),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn u16s(values: &[u16]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_be_bytes()).collect()
+  }
+
+  fn fixed(value: f64) -> [u8; 4] {
+    ((value * 65536.0) as i32).to_be_bytes()
+  }
+
+  /// Builds an `fvar` table with `wght` and `wdth` axes and two instances,
+  /// with or without `postScriptNameID` fields.
+  fn fvar(post_script_name_ids: Option<[u16; 2]>) -> Vec<u8> {
+    let instance_size = if post_script_name_ids.is_some() {
+      14
+    } else {
+      12
+    };
+    let mut data = u16s(&[1, 0, 16, 2, 2, 20, 2, instance_size]);
+
+    for (tag, min, default, max, flags, name_id) in [
+      (b"wght", 100.0, 400.0, 900.0, 0, 256),
+      (b"wdth", 75.0, 100.0, 125.0, 0x0001, 257),
+    ] {
+      data.extend(tag);
+      data.extend(fixed(min));
+      data.extend(fixed(default));
+      data.extend(fixed(max));
+      data.extend(u16s(&[flags, name_id]));
+    }
+
+    for (i, (subfamily_name_id, weight)) in [(258, 400.0), (259, 700.0)].into_iter().enumerate() {
+      data.extend(u16s(&[subfamily_name_id, 0]));
+      data.extend(fixed(weight));
+      data.extend(fixed(100.0));
+      if let Some(ids) = post_script_name_ids {
+        data.extend(u16s(&[ids[i]]));
+      }
+    }
+    data
+  }
+
+  #[test]
+  fn parses_axes() {
+    let table = Table::parse(&fvar(None), None).unwrap();
+    let axes = &table.axes;
+    assert_eq!(axes.len(), 2);
+    assert_eq!(axes[0].tag, "wght");
+    assert_eq!(
+      (axes[0].min_value, axes[0].default_value, axes[0].max_value),
+      (100.0, 400.0, 900.0)
+    );
+    assert!(!axes[0].hidden);
+    assert_eq!(axes[0].name_id, 256);
+    assert_eq!(axes[1].tag, "wdth");
+    assert!(axes[1].hidden);
+  }
+
+  #[test]
+  fn parses_instances_without_post_script_name_ids() {
+    let table = Table::parse(&fvar(None), None).unwrap();
+    let instances = &table.instances;
+    assert_eq!(instances.len(), 2);
+    assert_eq!(instances[0].subfamily_name_id, 258);
+    assert_eq!(instances[0].coordinates, [400.0, 100.0]);
+    assert_eq!(instances[1].subfamily_name_id, 259);
+    assert_eq!(instances[1].coordinates, [700.0, 100.0]);
+    assert!(instances
+      .iter()
+      .all(|instance| instance.post_script_name_id.is_none()));
+  }
+
+  #[test]
+  fn parses_instances_with_post_script_name_ids() {
+    // `0xFFFF` means that the instance has no PostScript name.
+    let table = Table::parse(&fvar(Some([260, 0xFFFF])), None).unwrap();
+    let instances = &table.instances;
+    assert_eq!(instances[0].coordinates, [400.0, 100.0]);
+    assert_eq!(instances[0].post_script_name_id, Some(260));
+    assert_eq!(instances[0].post_script_name, None);
+    assert_eq!(instances[1].coordinates, [700.0, 100.0]);
+    assert_eq!(instances[1].post_script_name_id, None);
+  }
+}
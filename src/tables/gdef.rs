@@ -0,0 +1,462 @@
+use std::collections::HashMap;
+
+use crate::stream::Stream;
+use napi_derive::napi;
+use ttf_parser::{Face, Tag};
+
+/// A [glyph class](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#glyph-class-definition-table).
+#[napi]
+pub enum GlyphClass {
+  /// A glyph missing from the class definition.
+  Unclassified,
+  /// A single character, spacing glyph.
+  Base,
+  /// A multiple character, spacing glyph.
+  Ligature,
+  /// A non-spacing combining glyph.
+  Mark,
+  /// A part of a single character, spacing glyph.
+  Component,
+}
+
+impl From<u16> for GlyphClass {
+  fn from(class: u16) -> GlyphClass {
+    match class {
+      1 => GlyphClass::Base,
+      2 => GlyphClass::Ligature,
+      3 => GlyphClass::Mark,
+      4 => GlyphClass::Component,
+      _ => GlyphClass::Unclassified,
+    }
+  }
+}
+
+/// A [caret value](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef#caret-value-tables)
+/// of any format.
+#[napi(object)]
+#[derive(Clone, Copy)]
+pub struct CaretValue {
+  /// Caret value table format, from 1 to 3.
+  pub format: u16,
+
+  /// X or Y coordinate of the caret in font units, for formats 1 and 3.
+  pub coordinate: Option<i16>,
+
+  /// Index of the outline point the caret goes through, for format 2.
+  pub point_index: Option<u16>,
+}
+
+/// The caret positions inside a ligature glyph.
+#[napi(object)]
+#[derive(Clone)]
+pub struct LigatureCarets {
+  /// The ligature glyph id.
+  pub glyph_id: u16,
+
+  /// One caret between every pair of components, in increasing coordinate
+  /// order.
+  pub carets: Vec<CaretValue>,
+}
+
+/// A [Glyph Definition Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gdef).
+#[napi(js_name = "GDEFTable", object)]
+#[derive(Clone)]
+pub struct Table {
+  /// Table version, e.g. `1.2`.
+  pub version: f64,
+
+  /// Mark glyph sets, each one a list of glyph ids in increasing order.
+  ///
+  /// `undefined` for tables older than version 1.2.
+  pub mark_glyph_sets: Option<Vec<Vec<u16>>>,
+
+  /// Caret positions of ligature glyphs, in increasing glyph id order.
+  pub ligature_carets: Vec<LigatureCarets>,
+
+  /// Checks that the table has an item variation store, used by variable
+  /// fonts to adjust `GPOS` values and caret positions.
+  pub has_variation_store: bool,
+}
+
+/// The subtable offsets of a `GDEF` table header.
+struct Header<'a> {
+  data: &'a [u8],
+  major_version: u16,
+  minor_version: u16,
+  glyph_class_def_offset: u16,
+  lig_caret_list_offset: u16,
+  mark_attach_class_def_offset: u16,
+  mark_glyph_sets_def_offset: Option<u16>,
+  item_var_store_offset: Option<u32>,
+}
+
+impl<'a> Header<'a> {
+  fn parse(face: &Face<'a>) -> Option<Self> {
+    let data = face.raw_face().table(Tag::from_bytes(b"GDEF"))?;
+    let mut s = Stream::new(data);
+    let major_version = s.read_u16()?;
+    let minor_version = s.read_u16()?;
+    if major_version != 1 {
+      return None;
+    }
+
+    let glyph_class_def_offset = s.read_u16()?;
+    s.skip(2)?; // attachListOffset
+    let lig_caret_list_offset = s.read_u16()?;
+    let mark_attach_class_def_offset = s.read_u16()?;
+    let mark_glyph_sets_def_offset = match minor_version {
+      0 | 1 => None,
+      _ => s.read_u16(),
+    };
+    let item_var_store_offset = match minor_version {
+      0..=2 => None,
+      _ => s.read_u32(),
+    };
+
+    Some(Self {
+      data,
+      major_version,
+      minor_version,
+      glyph_class_def_offset,
+      lig_caret_list_offset,
+      mark_attach_class_def_offset,
+      mark_glyph_sets_def_offset,
+      item_var_store_offset,
+    })
+  }
+
+  /// Returns the data of a subtable. Offsets of `0` mean that the subtable
+  /// is not present.
+  fn subtable(&self, offset: u16) -> Option<&'a [u8]> {
+    match offset {
+      0 => None,
+      offset => self.data.get(usize::from(offset)..),
+    }
+  }
+}
+
+impl Table {
+  pub fn new(face: &Face) -> Option<Self> {
+    let header = Header::parse(face)?;
+    let glyph_count = face.number_of_glyphs();
+
+    let mark_glyph_sets = match header.mark_glyph_sets_def_offset {
+      Some(0) => Some(Vec::new()),
+      Some(offset) => header
+        .data
+        .get(usize::from(offset)..)
+        .and_then(|data| parse_mark_glyph_sets(data, glyph_count)),
+      None => None,
+    };
+    let ligature_carets = header
+      .subtable(header.lig_caret_list_offset)
+      .and_then(|data| parse_ligature_carets(data, glyph_count))
+      .unwrap_or_default();
+
+    Some(Self {
+      version: f64::from(header.major_version) + f64::from(header.minor_version) / 10.0,
+      mark_glyph_sets,
+      ligature_carets,
+      has_variation_store: header
+        .item_var_store_offset
+        .is_some_and(|offset| offset != 0),
+    })
+  }
+}
+
+/// Returns the class of every glyph, indexed by glyph id, or `None` when the
+/// `GDEF` table has no glyph class definition.
+pub fn glyph_classes(face: &Face) -> Option<Vec<GlyphClass>> {
+  let header = Header::parse(face)?;
+  let classes = parse_class_definition(
+    header.subtable(header.glyph_class_def_offset)?,
+    face.number_of_glyphs(),
+  )?;
+
+  Some(classes.into_iter().map(GlyphClass::from).collect())
+}
+
+/// Returns the mark attachment class of every glyph, indexed by glyph id, or
+/// `None` when the `GDEF` table has no mark attachment class definition.
+pub fn mark_attachment_classes(face: &Face) -> Option<Vec<u16>> {
+  let header = Header::parse(face)?;
+  parse_class_definition(
+    header.subtable(header.mark_attach_class_def_offset)?,
+    face.number_of_glyphs(),
+  )
+}
+
+/// Coverage indices are `u16`, so entries past this length can never be
+/// referenced.
+const MAX_COVERAGE_LEN: usize = 1 << 16;
+
+/// Parses a [coverage table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#coverage-table)
+/// into its glyph ids, in coverage index order.
+///
+/// Ranges are clamped to the glyphs of the face.
+fn parse_coverage(data: &[u8], glyph_count: u16) -> Option<Vec<u16>> {
+  let mut s = Stream::new(data);
+  let format = s.read_u16()?;
+  let count = s.read_u16()?;
+  match format {
+    1 => (0..count).map(|_| s.read_u16()).collect(),
+    2 => {
+      let mut glyphs = Vec::new();
+      for _ in 0..count {
+        let start = s.read_u16()?;
+        let end = s.read_u16()?.min(glyph_count.saturating_sub(1));
+        s.skip(2)?; // startCoverageIndex
+        let remaining = MAX_COVERAGE_LEN - glyphs.len();
+        glyphs.extend((start..=end).take(remaining));
+      }
+      Some(glyphs)
+    }
+    _ => None,
+  }
+}
+
+/// Parses a [class definition table](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#class-definition-table)
+/// into the class of every glyph. Glyphs not listed are in class `0`.
+fn parse_class_definition(data: &[u8], glyph_count: u16) -> Option<Vec<u16>> {
+  let mut classes = vec![0; glyph_count.into()];
+
+  let mut s = Stream::new(data);
+  match s.read_u16()? {
+    1 => {
+      let start_glyph_id = s.read_u16()?;
+      let count = s.read_u16()?;
+      for i in 0..count {
+        let class = s.read_u16()?;
+        if let Some(slot) = classes.get_mut(usize::from(start_glyph_id.checked_add(i)?)) {
+          *slot = class;
+        }
+      }
+    }
+    2 => {
+      let count = s.read_u16()?;
+      let mut ranges = (0..count)
+        .map(|_| Some((s.read_u16()?, s.read_u16()?, s.read_u16()?)))
+        .collect::<Option<Vec<_>>>()?;
+
+      // Ranges should be sorted and disjoint. Sorting them lets every glyph
+      // be assigned at most once, even when crafted ranges overlap.
+      ranges.sort_by_key(|&(start, _, _)| start);
+      let mut next = 0;
+      for (start, end, class) in ranges {
+        let start = usize::from(start).max(next);
+        let end = (usize::from(end) + 1).min(classes.len());
+        if start < end {
+          classes[start..end].fill(class);
+          next = end;
+        }
+      }
+    }
+    _ => return None,
+  }
+
+  Some(classes)
+}
+
+/// Parses a coverage table into its glyph ids, sorted and deduplicated.
+///
+/// Unlike [`parse_coverage`], ranges are merged instead of expanded one by
+/// one, so overlapping ranges cost nothing.
+fn parse_coverage_set(data: &[u8], glyph_count: u16) -> Option<Vec<u16>> {
+  let mut s = Stream::new(data);
+  let format = s.read_u16()?;
+  let count = s.read_u16()?;
+  let mut ranges = match format {
+    1 => (0..count)
+      .map(|_| s.read_u16().map(|id| (id, id)))
+      .collect::<Option<Vec<_>>>()?,
+    2 => (0..count)
+      .map(|_| {
+        let range = (s.read_u16()?, s.read_u16()?);
+        s.skip(2)?; // startCoverageIndex
+        Some(range)
+      })
+      .collect::<Option<Vec<_>>>()?,
+    _ => return None,
+  };
+
+  ranges.sort_unstable();
+  let mut glyphs = Vec::new();
+  let mut next = 0;
+  for (start, end) in ranges {
+    let start = start.max(next);
+    let end = end.min(glyph_count.saturating_sub(1));
+    if usize::from(start) < usize::from(glyph_count) && start <= end {
+      glyphs.extend(start..=end);
+      next = end.saturating_add(1);
+    }
+  }
+
+  Some(glyphs)
+}
+
+fn parse_mark_glyph_sets(data: &[u8], glyph_count: u16) -> Option<Vec<Vec<u16>>> {
+  let mut s = Stream::new(data);
+  if s.read_u16()? != 1 {
+    return None;
+  }
+
+  let count = s.read_u16()?;
+  // Sets can share a coverage table, so parse each one only once.
+  let mut sets: HashMap<u32, Vec<u16>> = HashMap::new();
+  (0..count)
+    .map(|_| {
+      let offset = s.read_u32()?;
+      if let Some(glyphs) = sets.get(&offset) {
+        return Some(glyphs.clone());
+      }
+
+      let glyphs = parse_coverage_set(data.get(offset as usize..)?, glyph_count)?;
+      sets.insert(offset, glyphs.clone());
+      Some(glyphs)
+    })
+    .collect()
+}
+
+fn parse_ligature_carets(data: &[u8], glyph_count: u16) -> Option<Vec<LigatureCarets>> {
+  let mut s = Stream::new(data);
+  let glyphs = parse_coverage(data.get(usize::from(s.read_u16()?)..)?, glyph_count)?;
+  let count = s.read_u16()?;
+
+  let mut ligatures = Vec::with_capacity(count.into());
+  for &glyph_id in glyphs.iter().take(count.into()) {
+    // Caret value offsets are relative to the start of the ligature glyph table.
+    let ligature = data.get(usize::from(s.read_u16()?)..)?;
+    let mut ls = Stream::new(ligature);
+    let caret_count = ls.read_u16()?;
+    let carets = (0..caret_count)
+      .map(|_| parse_caret_value(&mut Stream::new_at(ligature, ls.read_u16()?.into())?))
+      .collect::<Option<Vec<_>>>()?;
+
+    ligatures.push(LigatureCarets { glyph_id, carets });
+  }
+
+  ligatures.sort_unstable_by_key(|ligature| ligature.glyph_id);
+  Some(ligatures)
+}
+
+fn parse_caret_value(s: &mut Stream) -> Option<CaretValue> {
+  let format = s.read_u16()?;
+  let mut caret = CaretValue {
+    format,
+    coordinate: None,
+    point_index: None,
+  };
+
+  match format {
+    1 | 3 => caret.coordinate = Some(s.read_i16()?),
+    2 => caret.point_index = Some(s.read_u16()?),
+    _ => return None,
+  }
+
+  Some(caret)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn u16s(values: &[u16]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_be_bytes()).collect()
+  }
+
+  #[test]
+  fn parses_class_definition_format_1() {
+    let data = u16s(&[1, 2, 3, 1, 2, 3]);
+    assert_eq!(
+      parse_class_definition(&data, 6),
+      Some(vec![0, 0, 1, 2, 3, 0])
+    );
+
+    // Glyphs past the end of the face are ignored.
+    let data = u16s(&[1, 4, 3, 1, 2, 3]);
+    assert_eq!(
+      parse_class_definition(&data, 6),
+      Some(vec![0, 0, 0, 0, 1, 2])
+    );
+  }
+
+  #[test]
+  fn parses_class_definition_format_2() {
+    let data = u16s(&[2, 2, 4, 0xFFFF, 3, 1, 2, 1]);
+    assert_eq!(
+      parse_class_definition(&data, 6),
+      Some(vec![0, 1, 1, 0, 3, 3])
+    );
+
+    // Overlapping ranges assign every glyph once, in start order.
+    let data = u16s(&[2, 2, 0, 3, 1, 2, 5, 2]);
+    assert_eq!(
+      parse_class_definition(&data, 6),
+      Some(vec![1, 1, 1, 1, 2, 2])
+    );
+  }
+
+  #[test]
+  fn rejects_invalid_class_definitions() {
+    assert_eq!(parse_class_definition(&u16s(&[3, 0]), 6), None);
+    assert_eq!(parse_class_definition(&u16s(&[2, 1, 0]), 6), None);
+  }
+
+  #[test]
+  fn parses_ligature_carets() {
+    // coverageOffset, ligGlyphCount, ligGlyphOffsets.
+    let mut data = u16s(&[8, 2, 16, 32]);
+    // Coverage of glyphs 7 and 5, in that order.
+    data.extend(u16s(&[1, 2, 7, 5]));
+    // Glyph 7: a format 1 caret and a format 3 caret with a device table.
+    data.extend(u16s(&[2, 6, 10, 1, 300, 3, -20i16 as u16, 0]));
+    // Glyph 5: a format 2 caret.
+    data.extend(u16s(&[1, 4, 2, 12]));
+
+    let ligatures = parse_ligature_carets(&data, 10).unwrap();
+    let carets = |ligature: &LigatureCarets| {
+      ligature
+        .carets
+        .iter()
+        .map(|caret| (caret.format, caret.coordinate, caret.point_index))
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(
+      ligatures
+        .iter()
+        .map(|ligature| ligature.glyph_id)
+        .collect::<Vec<_>>(),
+      [5, 7]
+    );
+    assert_eq!(carets(&ligatures[0]), [(2, None, Some(12))]);
+    assert_eq!(
+      carets(&ligatures[1]),
+      [(1, Some(300), None), (3, Some(-20), None)]
+    );
+  }
+
+  #[test]
+  fn rejects_unknown_caret_formats() {
+    assert!(parse_caret_value(&mut Stream::new(&u16s(&[4, 0]))).is_none());
+  }
+
+  #[test]
+  fn parses_mark_glyph_sets() {
+    // format, markGlyphSetCount, coverageOffsets. The last set shares the
+    // coverage of the first one.
+    let mut data = u16s(&[1, 3]);
+    data.extend(16u32.to_be_bytes());
+    data.extend(26u32.to_be_bytes());
+    data.extend(16u32.to_be_bytes());
+    // Unsorted format 1 coverage.
+    data.extend(u16s(&[1, 3, 9, 2, 4]));
+    // Overlapping format 2 coverage, past the end of the face.
+    data.extend(u16s(&[2, 2, 3, 5, 0, 4, 100, 3]));
+
+    assert_eq!(
+      parse_mark_glyph_sets(&data, 10),
+      Some(vec![vec![2, 4, 9], (3..=9).collect(), vec![2, 4, 9]])
+    );
+    assert_eq!(parse_mark_glyph_sets(&u16s(&[2, 0]), 10), None);
+  }
+}
@@ -1,5 +1,4 @@
 use super::Rect;
-use napi::bindgen_prelude::*;
 use napi_derive::napi;
 use ttf_parser::head;
 
@@ -0,0 +1,445 @@
+use crate::stream::Stream;
+use napi_derive::napi;
+use ttf_parser::opentype_layout::Coverage;
+use ttf_parser::{name, opentype_layout, Face, GlyphId, Tag};
+
+use super::name::find_name;
+
+/// A [language system](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#language-system-table).
+#[napi(object)]
+#[derive(Clone)]
+pub struct LanguageSystem {
+  /// Language system tag, e.g. `TRK `.
+  pub tag: String,
+
+  /// Index of a feature required by the language system.
+  pub required_feature: Option<u16>,
+
+  /// Indices of the features of the language system.
+  pub feature_indices: Vec<u16>,
+}
+
+impl From<opentype_layout::LanguageSystem<'_>> for LanguageSystem {
+  fn from(language: opentype_layout::LanguageSystem) -> LanguageSystem {
+    LanguageSystem {
+      tag: language.tag.to_string(),
+      required_feature: language.required_feature,
+      feature_indices: language.feature_indices.into_iter().collect(),
+    }
+  }
+}
+
+/// A [script](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#script-table).
+#[napi(object)]
+#[derive(Clone)]
+pub struct Script {
+  /// Script tag, e.g. `latn`.
+  pub tag: String,
+
+  /// Default language system of the script.
+  pub default_language: Option<LanguageSystem>,
+
+  /// Other language systems of the script.
+  pub languages: Vec<LanguageSystem>,
+}
+
+/// [Stylistic set parameters](https://docs.microsoft.com/en-us/typography/opentype/spec/features_pt#ss01---ss20).
+#[napi(object)]
+#[derive(Clone)]
+pub struct StylisticSetParams {
+  /// UI name ID in the `name` table.
+  pub ui_name_id: u16,
+
+  /// UI name, e.g. `Alternate a`, `undefined` when not present in the `name` table.
+  pub ui_name: Option<String>,
+}
+
+/// [Character variant parameters](https://docs.microsoft.com/en-us/typography/opentype/spec/features_ae#cv01-cv99).
+#[napi(object)]
+#[derive(Clone)]
+pub struct CharacterVariantParams {
+  /// UI label name ID in the `name` table, `0` when not set.
+  pub ui_label_name_id: u16,
+
+  /// UI label, `undefined` when not set or not present in the `name` table.
+  pub ui_label: Option<String>,
+
+  /// Tooltip name ID in the `name` table, `0` when not set.
+  pub tooltip_name_id: u16,
+
+  /// Tooltip, `undefined` when not set or not present in the `name` table.
+  pub tooltip: Option<String>,
+
+  /// Sample text name ID in the `name` table, `0` when not set.
+  pub sample_text_name_id: u16,
+
+  /// Sample text, `undefined` when not set or not present in the `name` table.
+  pub sample_text: Option<String>,
+
+  /// Labels of the named parameters, in order.
+  pub param_ui_labels: Vec<String>,
+
+  /// Unicode codepoints the feature applies to.
+  pub characters: Vec<u32>,
+}
+
+/// [Optical size parameters](https://docs.microsoft.com/en-us/typography/opentype/spec/features_pt#size).
+#[napi(object)]
+#[derive(Clone)]
+pub struct SizeParams {
+  /// Design size in points.
+  pub design_size: f64,
+
+  /// Identifies the fonts of a family sharing the same size range.
+  pub subfamily_identifier: u16,
+
+  /// Subfamily name ID in the `name` table, `0` when not set.
+  pub subfamily_name_id: u16,
+
+  /// Subfamily name, `undefined` when not set or not present in the `name` table.
+  pub subfamily_name: Option<String>,
+
+  /// Smallest size of the intended range in points, exclusive.
+  pub range_start: f64,
+
+  /// Largest size of the intended range in points, inclusive.
+  pub range_end: f64,
+}
+
+/// A [feature](https://docs.microsoft.com/en-us/typography/opentype/spec/chapter2#feature-table).
+#[napi(object)]
+#[derive(Clone)]
+pub struct Feature {
+  /// Feature tag, e.g. `liga`.
+  pub tag: String,
+
+  /// Indices of the lookups of the feature.
+  pub lookup_indices: Vec<u16>,
+
+  /// Parameters of a stylistic set feature (`ss01` to `ss20`).
+  pub stylistic_set: Option<StylisticSetParams>,
+
+  /// Parameters of a character variant feature (`cv01` to `cv99`).
+  pub character_variant: Option<CharacterVariantParams>,
+
+  /// Parameters of the `size` feature.
+  pub size: Option<SizeParams>,
+}
+
+/// A [Glyph Substitution Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gsub)
+/// or a [Glyph Positioning Table](https://docs.microsoft.com/en-us/typography/opentype/spec/gpos).
+#[napi(object)]
+#[derive(Clone)]
+pub struct LayoutTable {
+  /// A list of scripts.
+  pub scripts: Vec<Script>,
+
+  /// A list of features, referenced by index from language systems.
+  pub features: Vec<Feature>,
+
+  /// Number of lookups.
+  pub lookup_count: u16,
+}
+
+impl LayoutTable {
+  pub fn new(face: &Face, tag: Tag, table: Option<opentype_layout::LayoutTable>) -> Option<Self> {
+    let table = table?;
+    let names = face.tables().name;
+    let params = face
+      .raw_face()
+      .table(tag)
+      .and_then(|data| feature_params(data, table.features.len()));
+
+    let scripts = table
+      .scripts
+      .into_iter()
+      .map(|script| Script {
+        tag: script.tag.to_string(),
+        default_language: script.default_language.map(LanguageSystem::from),
+        languages: script
+          .languages
+          .into_iter()
+          .map(LanguageSystem::from)
+          .collect(),
+      })
+      .collect();
+
+    let features = table
+      .features
+      .into_iter()
+      .enumerate()
+      .map(|(i, feature)| {
+        let mut feature = Feature {
+          tag: feature.tag.to_string(),
+          lookup_indices: feature.lookup_indices.into_iter().collect(),
+          stylistic_set: None,
+          character_variant: None,
+          size: None,
+        };
+        if let Some(data) = params.as_ref().and_then(|params| params[i]) {
+          parse_feature_params(&mut feature, data, names);
+        }
+        feature
+      })
+      .collect();
+
+    Some(Self {
+      scripts,
+      features,
+      lookup_count: table.lookups.len(),
+    })
+  }
+}
+
+/// Returns the glyphs of a coverage table below `glyph_count`, sorted and
+/// deduplicated.
+///
+/// Ranges are merged instead of expanded one by one, so crafted overlapping
+/// ranges cannot blow up.
+pub fn coverage_glyphs(coverage: Coverage, glyph_count: u16) -> Vec<GlyphId> {
+  let mut ranges: Vec<(u16, u16)> = match coverage {
+    Coverage::Format1 { glyphs } => glyphs.into_iter().map(|g| (g.0, g.0)).collect(),
+    Coverage::Format2 { records } => records
+      .into_iter()
+      .map(|record| (record.start.0, record.end.0))
+      .collect(),
+  };
+  ranges.sort_unstable();
+
+  let mut glyphs = Vec::new();
+  let mut next = 0;
+  for (start, end) in ranges {
+    let start = start.max(next);
+    let end = end.min(glyph_count.saturating_sub(1));
+    if start < glyph_count && start <= end {
+      glyphs.extend((start..=end).map(GlyphId));
+      next = end.saturating_add(1);
+    }
+  }
+  glyphs
+}
+
+/// Returns the data of the feature parameters of every feature, which
+/// `ttf_parser` does not expose.
+fn feature_params(data: &[u8], count: u16) -> Option<Vec<Option<&[u8]>>> {
+  let mut s = Stream::new(data);
+  s.skip(6)?; // majorVersion, minorVersion, scriptListOffset
+  let feature_list = data.get(usize::from(s.read_u16()?)..)?;
+
+  let mut s = Stream::new(feature_list);
+  s.skip(2)?; // featureCount
+  let mut params = Vec::with_capacity(count.into());
+  for _ in 0..count {
+    s.skip(4)?; // featureTag
+    let feature_offset = usize::from(s.read_u16()?);
+    // The offset is relative to the start of the feature table.
+    let params_offset = Stream::new_at(feature_list, feature_offset)?.read_u16()?;
+    params.push(match params_offset {
+      0 => None,
+      offset => feature_list.get(feature_offset + usize::from(offset)..),
+    });
+  }
+
+  Some(params)
+}
+
+fn parse_feature_params(feature: &mut Feature, data: &[u8], names: Option<name::Table>) {
+  let tag = feature.tag.as_bytes();
+  let is_numbered =
+    |prefix: &[u8]| tag.starts_with(prefix) && tag[2..].iter().all(u8::is_ascii_digit);
+  let mut s = Stream::new(data);
+
+  if is_numbered(b"ss") {
+    feature.stylistic_set = parse_stylistic_set(&mut s, names);
+  } else if is_numbered(b"cv") {
+    feature.character_variant = parse_character_variant(&mut s, names);
+  } else if tag == b"size" {
+    feature.size = parse_size(&mut s, names);
+  }
+}
+
+fn parse_stylistic_set(s: &mut Stream, names: Option<name::Table>) -> Option<StylisticSetParams> {
+  s.skip(2)?; // version
+  let ui_name_id = s.read_u16()?;
+
+  Some(StylisticSetParams {
+    ui_name_id,
+    ui_name: find_name(names, ui_name_id),
+  })
+}
+
+fn parse_character_variant(
+  s: &mut Stream,
+  names: Option<name::Table>,
+) -> Option<CharacterVariantParams> {
+  s.skip(2)?; // format
+  let ui_label_name_id = s.read_u16()?;
+  let tooltip_name_id = s.read_u16()?;
+  let sample_text_name_id = s.read_u16()?;
+  let num_named_parameters = s.read_u16()?;
+  let first_param_ui_label_name_id = s.read_u16()?;
+  let char_count = s.read_u16()?;
+  let characters = (0..char_count)
+    .map(|_| {
+      let bytes = s.read_bytes(3)?;
+      Some(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]))
+    })
+    .collect::<Option<Vec<_>>>()?;
+
+  // Named parameters use consecutive name IDs.
+  let param_ui_labels = (0..num_named_parameters)
+    .filter_map(|i| first_param_ui_label_name_id.checked_add(i))
+    .map(|id| find_name(names, id).unwrap_or_default())
+    .collect();
+
+  // Name ID `0` means that the string is not set.
+  let optional_name = |id: u16| match id {
+    0 => None,
+    id => find_name(names, id),
+  };
+
+  Some(CharacterVariantParams {
+    ui_label_name_id,
+    ui_label: optional_name(ui_label_name_id),
+    tooltip_name_id,
+    tooltip: optional_name(tooltip_name_id),
+    sample_text_name_id,
+    sample_text: optional_name(sample_text_name_id),
+    param_ui_labels,
+    characters,
+  })
+}
+
+fn parse_size(s: &mut Stream, names: Option<name::Table>) -> Option<SizeParams> {
+  // Sizes are stored in decipoints.
+  let design_size = f64::from(s.read_u16()?) / 10.0;
+  let subfamily_identifier = s.read_u16()?;
+  let subfamily_name_id = s.read_u16()?;
+  let range_start = f64::from(s.read_u16()?) / 10.0;
+  let range_end = f64::from(s.read_u16()?) / 10.0;
+
+  Some(SizeParams {
+    design_size,
+    subfamily_identifier,
+    subfamily_name_id,
+    subfamily_name: match subfamily_name_id {
+      0 => None,
+      id => find_name(names, id),
+    },
+    range_start,
+    range_end,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn u16s(values: &[u16]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_be_bytes()).collect()
+  }
+
+  fn feature(tag: &str) -> Feature {
+    Feature {
+      tag: tag.to_owned(),
+      lookup_indices: Vec::new(),
+      stylistic_set: None,
+      character_variant: None,
+      size: None,
+    }
+  }
+
+  /// Builds a layout table whose feature list holds `ss01`, `cv01`, `size`
+  /// and `liga` features, only the last one without parameters.
+  fn layout() -> Vec<u8> {
+    let mut cv01 = u16s(&[0, 257, 0, 258, 2, 300, 2]);
+    cv01.extend([0x00, 0x00, 0x61, 0x01, 0xF6, 0x00]);
+    let features: [(&[u8; 4], Vec<u8>); 4] = [
+      (b"ss01", u16s(&[0, 256])),
+      (b"cv01", cv01),
+      (b"size", u16s(&[100, 1, 0, 80, 120])),
+      (b"liga", Vec::new()),
+    ];
+
+    // majorVersion, minorVersion, scriptListOffset, featureListOffset,
+    // lookupListOffset.
+    let mut data = u16s(&[1, 0, 0, 10, 0]);
+    data.extend(u16s(&[features.len() as u16]));
+    let mut tables = Vec::new();
+    let mut offset = 2 + features.len() * 6;
+    for (tag, params) in &features {
+      data.extend(*tag);
+      data.extend(u16s(&[offset as u16]));
+      // featureParamsOffset, relative to the feature table, and an empty
+      // lookupListIndices.
+      let params_offset = if params.is_empty() { 0 } else { 4 };
+      tables.extend(u16s(&[params_offset, 0]));
+      tables.extend(params);
+      offset += 4 + params.len();
+    }
+    data.extend(tables);
+    data
+  }
+
+  fn parse(tag: &str, params: Option<&[u8]>) -> Feature {
+    let mut feature = feature(tag);
+    if let Some(data) = params {
+      parse_feature_params(&mut feature, data, None);
+    }
+    feature
+  }
+
+  #[test]
+  fn finds_feature_params() {
+    let data = layout();
+    let params = feature_params(&data, 4).unwrap();
+    assert_eq!(params.len(), 4);
+    assert_eq!(params[0].map(|p| &p[..4]), Some(&u16s(&[0, 256])[..]));
+    assert!(params[1].is_some());
+    assert!(params[2].is_some());
+    assert_eq!(params[3], None);
+
+    assert!(feature_params(&data[..20], 4).is_none());
+  }
+
+  #[test]
+  fn parses_stylistic_set_params() {
+    let data = layout();
+    let params = feature_params(&data, 4).unwrap();
+    let feature = parse("ss01", params[0]);
+    let stylistic_set = feature.stylistic_set.unwrap();
+    assert_eq!(stylistic_set.ui_name_id, 256);
+    assert_eq!(stylistic_set.ui_name, None);
+    assert!(feature.character_variant.is_none());
+    assert!(feature.size.is_none());
+
+    // `ssty` is not a stylistic set.
+    assert!(parse("ssty", params[0]).stylistic_set.is_none());
+  }
+
+  #[test]
+  fn parses_character_variant_params() {
+    let data = layout();
+    let params = feature_params(&data, 4).unwrap();
+    let character_variant = parse("cv01", params[1]).character_variant.unwrap();
+    assert_eq!(character_variant.ui_label_name_id, 257);
+    assert_eq!(character_variant.tooltip_name_id, 0);
+    assert_eq!(character_variant.tooltip, None);
+    assert_eq!(character_variant.sample_text_name_id, 258);
+    assert_eq!(character_variant.param_ui_labels, ["", ""]);
+    assert_eq!(character_variant.characters, [0x61, 0x1F600]);
+  }
+
+  #[test]
+  fn parses_size_params() {
+    let data = layout();
+    let params = feature_params(&data, 4).unwrap();
+    let size = parse("size", params[2]).size.unwrap();
+    assert_eq!(size.design_size, 10.0);
+    assert_eq!(size.subfamily_identifier, 1);
+    assert_eq!(size.subfamily_name_id, 0);
+    assert_eq!(size.subfamily_name, None);
+    assert_eq!(size.range_start, 8.0);
+    assert_eq!(size.range_end, 12.0);
+  }
+}
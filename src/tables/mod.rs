@@ -1,9 +1,14 @@
+pub mod cmap;
+pub mod fvar;
+pub mod gdef;
 pub mod head;
 pub mod hhea;
+pub mod layout;
 pub mod maxp;
 pub mod name;
 pub mod os2;
-use napi::bindgen_prelude::*;
+pub mod post;
+pub mod stat;
 
 use napi_derive::napi;
 use ttf_parser::{Face, LineMetrics as PLineMetrics, Rect as PRect, Weight as PWeight};
@@ -23,6 +28,15 @@ pub struct Tables {
 
   // / https://docs.microsoft.com/en-us/typography/opentype/spec/name).
   pub name: Option<crate::tables::name::Table>,
+
+  /// A [Character to Glyph Index Mapping Table](https://docs.microsoft.com/en-us/typography/opentype/spec/cmap).
+  pub cmap: Option<crate::tables::cmap::Table>,
+
+  /// A [PostScript Table](https://docs.microsoft.com/en-us/typography/opentype/spec/post).
+  pub post: Option<crate::tables::post::Table>,
+
+  /// A [Font Variations Table](https://docs.microsoft.com/en-us/typography/opentype/spec/fvar).
+  pub fvar: Option<crate::tables::fvar::Table>,
 }
 
 impl Tables {
@@ -35,6 +49,9 @@ impl Tables {
 
     let os2 = crate::tables::os2::Table::new(face_tables.os2);
     let name = crate::tables::name::Table::new(face_tables.name);
+    let cmap = crate::tables::cmap::Table::new(face_tables.cmap);
+    let post = crate::tables::post::Table::new(face, face_tables.post);
+    let fvar = crate::tables::fvar::Table::new(face);
 
     Self {
       head,
@@ -42,6 +59,9 @@ impl Tables {
       maxp,
       os2,
       name,
+      cmap,
+      post,
+      fvar,
     }
   }
 }
@@ -1,4 +1,3 @@
-use napi::{bindgen_prelude::*, sys::Status};
 use napi_derive::napi;
 use ttf_parser::{name, LazyArray16};
 
@@ -107,6 +106,27 @@ pub struct NameRecord {
   pub name: String,
 }
 
+/// Windows English (United States), the language names are preferred in.
+const WINDOWS_ENGLISH_US: u16 = 0x0409;
+
+/// Finds a Unicode-encoded name by its ID, preferring US English records.
+pub fn find_name(table: Option<name::Table>, name_id: u16) -> Option<String> {
+  let mut fallback = None;
+  for name in table?.names.into_iter().filter(|n| n.name_id == name_id) {
+    if name.platform_id == name::PlatformId::Windows && name.language_id == WINDOWS_ENGLISH_US {
+      if let Some(name) = name.to_string() {
+        return Some(name);
+      }
+    }
+
+    if fallback.is_none() {
+      fallback = name.to_string();
+    }
+  }
+
+  fallback
+}
+
 /// A [Naming Table](https://docs.microsoft.com/en-us/typography/opentype/spec/name).
 #[napi(js_name = "NAMETable", object)]
 #[derive(Clone)]
@@ -116,9 +136,7 @@ pub struct Table {
 
 impl Table {
   pub fn new(table: Option<name::Table>) -> Option<Self> {
-    let Some(table_names) = table else {
-            return None;
-        };
+    let table_names = table?;
     let names = table_names
       .names
       .into_iter()
@@ -1,5 +1,4 @@
 use crate::tables::{LineMetrics, Weight};
-use napi::bindgen_prelude::*;
 use napi_derive::napi;
 use ttf_parser::os2;
 
@@ -0,0 +1,66 @@
+use super::LineMetrics;
+use crate::stream::Stream;
+use napi_derive::napi;
+use ttf_parser::{post, Face, Tag};
+
+/// A [PostScript Table](https://docs.microsoft.com/en-us/typography/opentype/spec/post).
+#[napi(js_name = "POSTTable", object)]
+#[derive(Clone)]
+pub struct Table {
+  /// Table version, e.g. `2` or `2.5`.
+  pub version: f64,
+
+  /// Italic angle in counter-clockwise degrees from the vertical.
+  pub italic_angle: f64,
+
+  /// Underline metrics.
+  pub underline_metrics: LineMetrics,
+
+  /// Checks that the face is monospaced.
+  pub is_fixed_pitch: bool,
+
+  /// Minimum memory usage when the face is downloaded as a Type 42 font.
+  pub min_mem_type42: u32,
+
+  /// Maximum memory usage when the face is downloaded as a Type 42 font.
+  pub max_mem_type42: u32,
+
+  /// Minimum memory usage when the face is downloaded as a Type 1 font.
+  pub min_mem_type1: u32,
+
+  /// Maximum memory usage when the face is downloaded as a Type 1 font.
+  pub max_mem_type1: u32,
+}
+
+impl Table {
+  pub fn new(face: &Face, table: Option<post::Table>) -> Option<Self> {
+    let table = table?;
+    let data = face.raw_face().table(Tag::from_bytes(b"post"))?;
+    let mut s = Stream::new(data);
+    let version = s.read_u32()?;
+    // Skip italicAngle, underlinePosition, underlineThickness and isFixedPitch,
+    // which `ttf_parser` already exposes.
+    s.skip(12)?;
+    let min_mem_type42 = s.read_u32()?;
+    let max_mem_type42 = s.read_u32()?;
+    let min_mem_type1 = s.read_u32()?;
+    let max_mem_type1 = s.read_u32()?;
+
+    Some(Self {
+      version: parse_version(version),
+      italic_angle: table.italic_angle.into(),
+      underline_metrics: table.underline_metrics.into(),
+      is_fixed_pitch: table.is_monospaced,
+      min_mem_type42,
+      max_mem_type42,
+      min_mem_type1,
+      max_mem_type1,
+    })
+  }
+}
+
+/// Converts a `post` version number, where the minor version is stored as a
+/// single hexadecimal digit (`0x00025000` is 2.5), to a number.
+fn parse_version(version: u32) -> f64 {
+  f64::from(version >> 16) + f64::from((version >> 12) & 0xF) / 10.0
+}
@@ -0,0 +1,351 @@
+use crate::stream::Stream;
+use napi_derive::napi;
+use ttf_parser::{name, Face, Tag};
+
+use super::name::find_name;
+
+/// The axis value applies to older fonts of the family as well.
+const OLDER_SIBLING_FONT_ATTRIBUTE: u16 = 0x0001;
+
+/// The value name can be omitted when composing a style name.
+const ELIDABLE_AXIS_VALUE_NAME: u16 = 0x0002;
+
+/// A [design axis](https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-records).
+#[napi(object)]
+#[derive(Clone)]
+pub struct DesignAxis {
+  /// Axis tag, e.g. `wght`.
+  pub tag: String,
+
+  /// Axis name ID in the `name` table.
+  pub name_id: u16,
+
+  /// Axis name, `undefined` when not present in the `name` table.
+  pub name: Option<String>,
+
+  /// Position of the axis when composing style names, lowest first.
+  pub ordering: u16,
+}
+
+/// A value of a single axis in a format 4 axis value.
+#[napi(object)]
+#[derive(Clone, Copy)]
+pub struct AxisValueRecord {
+  /// Index of the axis in the design axes.
+  pub axis_index: u16,
+
+  /// Axis value.
+  pub value: f64,
+}
+
+/// An [axis value](https://docs.microsoft.com/en-us/typography/opentype/spec/stat#axis-value-tables)
+/// of any format.
+///
+/// Fields not used by a format are `undefined`.
+#[napi(object)]
+#[derive(Clone)]
+pub struct AxisValue {
+  /// Axis value table format, from 1 to 4.
+  pub format: u16,
+
+  /// Index of the axis in the design axes. Not used by format 4.
+  pub axis_index: Option<u16>,
+
+  /// Raw axis value flags.
+  pub flags: u16,
+
+  /// Checks that the value applies to older fonts of the family as well.
+  pub older_sibling_font_attribute: bool,
+
+  /// Checks that the value name can be omitted when composing a style name,
+  /// e.g. `Regular`.
+  pub elidable: bool,
+
+  /// Value name ID in the `name` table.
+  pub value_name_id: u16,
+
+  /// Value name, e.g. `Semibold`, `undefined` when not present in the `name` table.
+  pub name: Option<String>,
+
+  /// Axis value of formats 1 and 3.
+  pub value: Option<f64>,
+
+  /// Nominal value of format 2.
+  pub nominal_value: Option<f64>,
+
+  /// Minimum value of the range of format 2.
+  pub range_min_value: Option<f64>,
+
+  /// Maximum value of the range of format 2.
+  pub range_max_value: Option<f64>,
+
+  /// Style-linked value of format 3, e.g. `700` for `400`.
+  pub linked_value: Option<f64>,
+
+  /// Values of every axis of format 4.
+  pub axis_values: Option<Vec<AxisValueRecord>>,
+}
+
+/// A [Style Attributes Table](https://docs.microsoft.com/en-us/typography/opentype/spec/stat).
+#[napi(js_name = "STATTable", object)]
+#[derive(Clone)]
+pub struct Table {
+  /// Design axes, in the order referenced by axis indices.
+  pub design_axes: Vec<DesignAxis>,
+
+  /// Axis values. Values of an unknown format are skipped.
+  pub axis_values: Vec<AxisValue>,
+
+  /// Name ID of the name used when every value is elided, e.g. `Regular`.
+  ///
+  /// `undefined` for version 1.0 tables.
+  pub elided_fallback_name_id: Option<u16>,
+
+  /// Name used when every value is elided, `undefined` when not present
+  /// in the `name` table.
+  pub elided_fallback_name: Option<String>,
+}
+
+impl Table {
+  pub fn new(face: &Face) -> Option<Self> {
+    let data = face.raw_face().table(Tag::from_bytes(b"STAT"))?;
+    Self::parse(data, face.tables().name)
+  }
+
+  fn parse(data: &[u8], names: Option<name::Table>) -> Option<Self> {
+    let mut s = Stream::new(data);
+    let major_version = s.read_u16()?;
+    let minor_version = s.read_u16()?;
+    if major_version != 1 {
+      return None;
+    }
+
+    let design_axis_size = s.read_u16()?;
+    let design_axis_count = s.read_u16()?;
+    let design_axes_offset = s.read_u32()?;
+    let axis_value_count = s.read_u16()?;
+    let axis_value_offsets_offset = s.read_u32()?;
+    let elided_fallback_name_id = match minor_version {
+      0 => None,
+      _ => s.read_u16(),
+    };
+
+    let mut s = Stream::new_at(data, design_axes_offset as usize)?;
+    let mut design_axes = Vec::with_capacity(design_axis_count.into());
+    for _ in 0..design_axis_count {
+      let mut record = Stream::new(s.read_bytes(design_axis_size.into())?);
+      let tag = Tag::from_bytes(record.read_bytes(4)?.try_into().ok()?);
+      let name_id = record.read_u16()?;
+      design_axes.push(DesignAxis {
+        tag: tag.to_string(),
+        name_id,
+        name: find_name(names, name_id),
+        ordering: record.read_u16()?,
+      });
+    }
+
+    // Axis value offsets are relative to the start of the offsets array.
+    let axis_values_data = data.get(axis_value_offsets_offset as usize..)?;
+    let mut s = Stream::new(axis_values_data);
+    let mut axis_values = Vec::with_capacity(axis_value_count.into());
+    for _ in 0..axis_value_count {
+      let offset = s.read_u16()?;
+      let mut value = Stream::new_at(axis_values_data, offset.into())?;
+      if let Some(value) = parse_axis_value(&mut value, names) {
+        axis_values.push(value);
+      }
+    }
+
+    Some(Self {
+      design_axes,
+      axis_values,
+      elided_fallback_name_id,
+      elided_fallback_name: elided_fallback_name_id.and_then(|id| find_name(names, id)),
+    })
+  }
+}
+
+fn parse_axis_value(s: &mut Stream, names: Option<name::Table>) -> Option<AxisValue> {
+  let format = s.read_u16()?;
+  let (axis_index, axis_count) = match format {
+    1..=3 => (Some(s.read_u16()?), None),
+    4 => (None, Some(s.read_u16()?)),
+    _ => return None,
+  };
+  let flags = s.read_u16()?;
+  let value_name_id = s.read_u16()?;
+
+  let mut axis_value = AxisValue {
+    format,
+    axis_index,
+    flags,
+    older_sibling_font_attribute: flags & OLDER_SIBLING_FONT_ATTRIBUTE != 0,
+    elidable: flags & ELIDABLE_AXIS_VALUE_NAME != 0,
+    value_name_id,
+    name: find_name(names, value_name_id),
+    value: None,
+    nominal_value: None,
+    range_min_value: None,
+    range_max_value: None,
+    linked_value: None,
+    axis_values: None,
+  };
+
+  match format {
+    1 => axis_value.value = Some(s.read_fixed()?),
+    2 => {
+      axis_value.nominal_value = Some(s.read_fixed()?);
+      axis_value.range_min_value = Some(s.read_fixed()?);
+      axis_value.range_max_value = Some(s.read_fixed()?);
+    }
+    3 => {
+      axis_value.value = Some(s.read_fixed()?);
+      axis_value.linked_value = Some(s.read_fixed()?);
+    }
+    _ => {
+      let records = (0..axis_count.unwrap_or(0))
+        .map(|_| {
+          Some(AxisValueRecord {
+            axis_index: s.read_u16()?,
+            value: s.read_fixed()?,
+          })
+        })
+        .collect::<Option<Vec<_>>>()?;
+      axis_value.axis_values = Some(records);
+    }
+  }
+
+  Some(axis_value)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn u16s(values: &[u16]) -> Vec<u8> {
+    values.iter().flat_map(|v| v.to_be_bytes()).collect()
+  }
+
+  fn fixed(value: f64) -> [u8; 4] {
+    ((value * 65536.0) as i32).to_be_bytes()
+  }
+
+  /// Builds a `STAT` table with a `wght` design axis and one axis value of
+  /// every format, plus one of an unknown format.
+  fn stat(minor_version: u16) -> Vec<u8> {
+    let header_size = if minor_version == 0 { 18 } else { 20 };
+    let mut values = Vec::new();
+    let mut offsets = Vec::new();
+    let value_offsets_size = 5 * 2;
+
+    // Format 1: Bold, linked to older fonts.
+    offsets.push((value_offsets_size + values.len()) as u16);
+    values.extend(u16s(&[1, 0, 0x0001, 257]));
+    values.extend(fixed(700.0));
+
+    // Format 2: Regular, elidable.
+    offsets.push((value_offsets_size + values.len()) as u16);
+    values.extend(u16s(&[2, 0, 0x0002, 258]));
+    values.extend(fixed(400.0));
+    values.extend(fixed(350.0));
+    values.extend(fixed(450.0));
+
+    // Format 3: Regular, linked to Bold.
+    offsets.push((value_offsets_size + values.len()) as u16);
+    values.extend(u16s(&[3, 0, 0, 258]));
+    values.extend(fixed(400.0));
+    values.extend(fixed(700.0));
+
+    // Format 4: two axis values.
+    offsets.push((value_offsets_size + values.len()) as u16);
+    values.extend(u16s(&[4, 2, 0, 259, 0]));
+    values.extend(fixed(-0.5));
+    values.extend(u16s(&[1]));
+    values.extend(fixed(100.0));
+
+    // Unknown format.
+    offsets.push((value_offsets_size + values.len()) as u16);
+    values.extend(u16s(&[5, 0, 0, 0]));
+
+    let mut data = u16s(&[1, minor_version, 8, 1]);
+    data.extend((header_size as u32).to_be_bytes()); // designAxesOffset
+    data.extend(u16s(&[offsets.len() as u16]));
+    data.extend((header_size as u32 + 8).to_be_bytes()); // offsetToAxisValueOffsets
+    if minor_version > 0 {
+      data.extend(u16s(&[2])); // elidedFallbackNameID
+    }
+    data.extend(b"wght");
+    data.extend(u16s(&[256, 0]));
+    data.extend(u16s(&offsets));
+    data.extend(values);
+    data
+  }
+
+  #[test]
+  fn parses_design_axes() {
+    let table = Table::parse(&stat(1), None).unwrap();
+    assert_eq!(table.design_axes.len(), 1);
+    let axis = &table.design_axes[0];
+    assert_eq!(axis.tag, "wght");
+    assert_eq!(axis.name_id, 256);
+    assert_eq!(axis.name, None);
+    assert_eq!(axis.ordering, 0);
+  }
+
+  #[test]
+  fn parses_axis_value_formats() {
+    let table = Table::parse(&stat(1), None).unwrap();
+    let values = &table.axis_values;
+    assert_eq!(
+      values.iter().map(|value| value.format).collect::<Vec<_>>(),
+      [1, 2, 3, 4]
+    );
+
+    assert_eq!(values[0].axis_index, Some(0));
+    assert!(values[0].older_sibling_font_attribute);
+    assert!(!values[0].elidable);
+    assert_eq!(values[0].value_name_id, 257);
+    assert_eq!(values[0].value, Some(700.0));
+    assert_eq!(values[0].linked_value, None);
+
+    assert!(values[1].elidable);
+    assert_eq!(values[1].nominal_value, Some(400.0));
+    assert_eq!(values[1].range_min_value, Some(350.0));
+    assert_eq!(values[1].range_max_value, Some(450.0));
+    assert_eq!(values[1].value, None);
+
+    assert_eq!(values[2].value, Some(400.0));
+    assert_eq!(values[2].linked_value, Some(700.0));
+    assert_eq!(values[2].flags, 0);
+
+    assert_eq!(values[3].axis_index, None);
+    assert_eq!(values[3].value_name_id, 259);
+    let records = values[3].axis_values.as_ref().unwrap();
+    assert_eq!(
+      records
+        .iter()
+        .map(|record| (record.axis_index, record.value))
+        .collect::<Vec<_>>(),
+      [(0, -0.5), (1, 100.0)]
+    );
+  }
+
+  #[test]
+  fn reads_elided_fallback_name_id_from_version_1_1() {
+    let table = Table::parse(&stat(1), None).unwrap();
+    assert_eq!(table.elided_fallback_name_id, Some(2));
+    assert_eq!(table.axis_values.len(), 4);
+
+    let table = Table::parse(&stat(0), None).unwrap();
+    assert_eq!(table.elided_fallback_name_id, None);
+    assert_eq!(table.elided_fallback_name, None);
+    assert_eq!(table.axis_values.len(), 4);
+  }
+
+  #[test]
+  fn rejects_unknown_major_versions() {
+    let mut data = stat(1);
+    data[1] = 2;
+    assert!(Table::parse(&data, None).is_none());
+  }
+}
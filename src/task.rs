@@ -0,0 +1,94 @@
+use napi::bindgen_prelude::*;
+use napi::JsError;
+
+use crate::{data::FontData, CustomError, ParsedFace, TTFParser};
+
+/// Rejects with the same `code` and message a synchronous parse would throw.
+fn reject_with(env: Env, err: CustomError) -> Error {
+  Error::from(JsError::from(Error::from(err)).into_unknown(env))
+}
+
+/// Parses a single face on the libuv threadpool.
+pub struct ParseTask {
+  buffer: Buffer,
+  index: u32,
+}
+
+impl ParseTask {
+  pub fn new(buffer: Buffer, index: u32) -> Self {
+    Self { buffer, index }
+  }
+}
+
+impl Task for ParseTask {
+  type Output = std::result::Result<TTFParser, CustomError>;
+  type JsValue = TTFParser;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    Ok(FontData::from_slice(&self.buffer).and_then(|data| TTFParser::parse(data, self.index)))
+  }
+
+  fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    output.map_err(|err| reject_with(env, err))
+  }
+}
+
+/// Memory-maps and parses a font file on the libuv threadpool.
+pub struct FromFileTask {
+  path: String,
+  index: u32,
+}
+
+impl FromFileTask {
+  pub fn new(path: String, index: u32) -> Self {
+    Self { path, index }
+  }
+}
+
+impl Task for FromFileTask {
+  type Output = std::result::Result<TTFParser, CustomError>;
+  type JsValue = TTFParser;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    Ok(TTFParser::from_file_impl(&self.path, self.index))
+  }
+
+  fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    output.map_err(|err| reject_with(env, err))
+  }
+}
+
+/// Parses the first face of many fonts on the libuv threadpool.
+pub struct ParseBatchTask {
+  buffers: Vec<Buffer>,
+}
+
+impl ParseBatchTask {
+  pub fn new(buffers: Vec<Buffer>) -> Self {
+    Self { buffers }
+  }
+}
+
+impl Task for ParseBatchTask {
+  type Output = Vec<std::result::Result<TTFParser, CustomError>>;
+  type JsValue = Vec<ParsedFace>;
+
+  fn compute(&mut self) -> Result<Self::Output> {
+    Ok(
+      self
+        .buffers
+        .iter()
+        .map(|buffer| FontData::from_slice(buffer).and_then(|data| TTFParser::parse(data, 0)))
+        .collect(),
+    )
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+    Ok(
+      output
+        .into_iter()
+        .map(|result| ParsedFace::new(0, result))
+        .collect(),
+    )
+  }
+}
@@ -0,0 +1,100 @@
+use napi_derive::napi;
+use ttf_parser::{Face, GlyphId};
+
+use crate::kerning;
+
+/// Options of `measureText`.
+#[napi(object)]
+#[derive(Clone, Copy, Default)]
+pub struct MeasureTextOptions {
+  /// Applies pair kerning from the legacy `kern` table. Defaults to `true`.
+  pub kerning: Option<bool>,
+}
+
+/// A bounding box in scaled units, y pointing up from the baseline.
+#[napi(object)]
+#[derive(Clone, Copy)]
+pub struct TextBounds {
+  pub x_min: f64,
+  pub y_min: f64,
+  pub x_max: f64,
+  pub y_max: f64,
+}
+
+/// The result of `measureText`, scaled to the requested font size.
+#[napi(object)]
+#[derive(Clone)]
+pub struct TextMeasurement {
+  /// The total advance width of the text.
+  pub width: f64,
+
+  /// The union of all glyph bounding boxes, `undefined` when nothing is inked.
+  pub ink_bounds: Option<TextBounds>,
+
+  /// The advance of every character, kerning with the next one included.
+  pub advances: Vec<f64>,
+
+  /// The x position of every character's origin.
+  pub positions: Vec<f64>,
+}
+
+/// Measures `text` laid out on a single line, one glyph per character.
+///
+/// Characters are mapped through `cmap`, falling back to `.notdef`.
+pub fn measure_text(
+  face: &Face,
+  text: &str,
+  font_size: f64,
+  options: MeasureTextOptions,
+) -> TextMeasurement {
+  let scale = font_size / f64::from(face.units_per_em());
+  let use_kerning = options.kerning.unwrap_or(true);
+
+  let glyphs: Vec<GlyphId> = text
+    .chars()
+    .map(|c| face.glyph_index(c).unwrap_or(GlyphId(0)))
+    .collect();
+
+  let mut advances = Vec::with_capacity(glyphs.len());
+  let mut positions = Vec::with_capacity(glyphs.len());
+  let mut ink_bounds: Option<TextBounds> = None;
+  let mut pen = 0i64;
+
+  for (index, &glyph_id) in glyphs.iter().enumerate() {
+    let mut advance = i64::from(face.glyph_hor_advance(glyph_id).unwrap_or(0));
+    if use_kerning {
+      if let Some(&next) = glyphs.get(index + 1) {
+        advance += i64::from(kerning::kern_table(face, glyph_id, next).unwrap_or(0));
+      }
+    }
+
+    if let Some(bbox) = face.glyph_bounding_box(glyph_id) {
+      let bounds = TextBounds {
+        x_min: (pen + i64::from(bbox.x_min)) as f64 * scale,
+        y_min: f64::from(bbox.y_min) * scale,
+        x_max: (pen + i64::from(bbox.x_max)) as f64 * scale,
+        y_max: f64::from(bbox.y_max) * scale,
+      };
+      ink_bounds = Some(match ink_bounds {
+        Some(ink) => TextBounds {
+          x_min: ink.x_min.min(bounds.x_min),
+          y_min: ink.y_min.min(bounds.y_min),
+          x_max: ink.x_max.max(bounds.x_max),
+          y_max: ink.y_max.max(bounds.y_max),
+        },
+        None => bounds,
+      });
+    }
+
+    positions.push(pen as f64 * scale);
+    advances.push(advance as f64 * scale);
+    pen += advance;
+  }
+
+  TextMeasurement {
+    width: pen as f64 * scale,
+    ink_bounds,
+    advances,
+    positions,
+  }
+}
@@ -0,0 +1,474 @@
+use ttf_parser::{Face, NormalizedCoordinate, Tag};
+
+use crate::stream::Stream;
+
+/// The number of axes `ttf_parser` stores coordinates for.
+const MAX_AXES: usize = 32;
+
+/// One in F2DOT14 units.
+const ONE: i32 = 16384;
+
+/// An [axis value map](https://docs.microsoft.com/en-us/typography/opentype/spec/avar#table-formats)
+/// as `(fromCoordinate, toCoordinate)` pairs in F2DOT14 units.
+type SegmentMap = Vec<(i16, i16)>;
+
+/// Converts a JS string to an axis tag, e.g. `wght`.
+///
+/// Shorter tags are padded with spaces. Returns `None` for empty tags and
+/// tags longer than 4 bytes.
+pub fn parse_tag(tag: &str) -> Option<Tag> {
+  match tag.len() {
+    1..=4 => Some(Tag::from_bytes_lossy(tag.as_bytes())),
+    _ => None,
+  }
+}
+
+/// A variation axis with its `avar` segment map.
+struct Axis {
+  tag: Tag,
+  min_value: f32,
+  def_value: f32,
+  max_value: f32,
+  segment_map: Option<SegmentMap>,
+}
+
+impl Axis {
+  /// Normalizes a user-space value to the -1..1 range, without `avar`.
+  ///
+  /// Matches `ttf_parser`, including the truncation to F2DOT14.
+  fn normalize_default(&self, value: f32) -> i16 {
+    let value = value.clamp(self.min_value, self.max_value);
+    let value = if value == self.def_value {
+      0.0
+    } else if value < self.def_value {
+      (value - self.def_value) / (self.def_value - self.min_value)
+    } else {
+      (value - self.def_value) / (self.max_value - self.def_value)
+    };
+
+    NormalizedCoordinate::from(value).get()
+  }
+
+  /// Converts a normalized coordinate in the -1..1 range back to user space,
+  /// without `avar`.
+  fn user_value(&self, coordinate: f32) -> f32 {
+    if coordinate < 0.0 {
+      self.def_value + coordinate * (self.def_value - self.min_value)
+    } else {
+      self.def_value + coordinate * (self.max_value - self.def_value)
+    }
+  }
+
+  /// Converts an F2DOT14 coordinate back to user space, without `avar`.
+  ///
+  /// The result is nudged by half a unit away from the default, so that
+  /// [`normalize_default`](Axis::normalize_default) truncates it back to `coordinate`.
+  fn denormalize_default(&self, coordinate: i16) -> f32 {
+    match coordinate {
+      0 => self.def_value,
+      c if c < 0 => self.user_value((f32::from(c) - 0.5) / ONE as f32),
+      c => self.user_value((f32::from(c) + 0.5) / ONE as f32),
+    }
+  }
+
+  /// Applies the `avar` segment map to a normalized coordinate.
+  fn map(&self, coordinate: i16) -> i16 {
+    match &self.segment_map {
+      Some(map) => map_value(map, coordinate),
+      None => coordinate,
+    }
+  }
+}
+
+/// The variation axes of a face.
+pub struct Axes {
+  axes: Vec<Axis>,
+}
+
+impl Axes {
+  pub fn new(face: &Face) -> Self {
+    let mut axes: Vec<Axis> = face
+      .variation_axes()
+      .into_iter()
+      .take(MAX_AXES)
+      .map(|axis| Axis {
+        tag: axis.tag,
+        min_value: axis.min_value,
+        def_value: axis.def_value,
+        max_value: axis.max_value,
+        segment_map: None,
+      })
+      .collect();
+
+    // Like `ttf_parser`, ignore an `avar` table that doesn't cover every axis.
+    if let Some(maps) = parse_avar(face).filter(|maps| maps.len() == axes.len()) {
+      for (axis, map) in axes.iter_mut().zip(maps) {
+        axis.segment_map = Some(map);
+      }
+    }
+
+    Self { axes }
+  }
+
+  /// Returns the index of an axis that coordinates can be set for.
+  pub fn position(&self, tag: Tag) -> Option<usize> {
+    self.axes.iter().position(|axis| axis.tag == tag)
+  }
+
+  /// Calls `set_variation` with the values to pass to `Face::set_variation`
+  /// so that a face ends up at `variations`.
+  ///
+  /// `Face::set_variation` re-applies the `avar` table to every coordinate on
+  /// each call, so an axis set before others would be mapped several times.
+  /// To compensate, each axis but the last is set to the value whose repeated
+  /// mapping yields the expected coordinate. Axes are set in `fvar` order, as
+  /// that value can be off by a unit when the exact one is not reachable.
+  pub fn apply(&self, variations: &[(Tag, f32)], mut set_variation: impl FnMut(Tag, f32)) {
+    let mut variations: Vec<(&Axis, f32)> = variations
+      .iter()
+      .filter_map(|&(tag, value)| Some((&self.axes[self.position(tag)?], value)))
+      .collect();
+    variations.sort_by_key(|&(axis, _)| self.position(axis.tag));
+
+    for (i, &(axis, value)) in variations.iter().enumerate() {
+      let later_calls = variations.len() - 1 - i;
+      let value = match axis.segment_map {
+        Some(_) if later_calls > 0 => {
+          let target = axis.map(axis.normalize_default(value));
+          let coordinate = preimage(target, |c| (0..=later_calls).fold(c, |c, _| axis.map(c)));
+          axis.denormalize_default(coordinate)
+        }
+        _ => value,
+      };
+
+      set_variation(axis.tag, value);
+    }
+  }
+
+  /// Returns the tag of the axis at `index`.
+  pub fn tag(&self, index: usize) -> Tag {
+    self.axes[index].tag
+  }
+
+  /// Converts a user-space value of the axis at `index` to a normalized
+  /// coordinate in F2DOT14 units, applying `avar`.
+  pub fn normalize(&self, index: usize, value: f32) -> i16 {
+    let axis = &self.axes[index];
+    axis.map(axis.normalize_default(value))
+  }
+
+  /// Converts a normalized coordinate of the axis at `index` back to user
+  /// space, undoing `avar`.
+  pub fn denormalize(&self, index: usize, coordinate: f32) -> f32 {
+    let axis = &self.axes[index];
+    let coordinate = match &axis.segment_map {
+      Some(map) => unmap_value(map, coordinate),
+      None => coordinate,
+    };
+
+    axis.user_value(coordinate.clamp(-1.0, 1.0))
+  }
+}
+
+/// Sets variation coordinates on `face`, in order.
+pub fn apply(face: &mut Face, variations: &[(Tag, f32)]) {
+  Axes::new(face).apply(variations, |tag, value| {
+    face.set_variation(tag, value);
+  });
+}
+
+/// Finds the coordinate that `f`, a non-decreasing function, maps closest to `target`.
+fn preimage(target: i16, f: impl Fn(i16) -> i16) -> i16 {
+  let (mut low, mut high) = (-ONE as i16, ONE as i16);
+  while low < high {
+    let mid = (i32::from(low) + i32::from(high)).div_euclid(2) as i16;
+    if f(mid) < target {
+      low = mid + 1;
+    } else {
+      high = mid;
+    }
+  }
+
+  // `low` is the first coordinate mapped to `target` or above it.
+  let distance = |c: i16| (i32::from(f(c)) - i32::from(target)).abs();
+  if low > -ONE as i16 && distance(low) > distance(low - 1) {
+    low - 1
+  } else {
+    low
+  }
+}
+
+fn parse_avar(face: &Face) -> Option<Vec<SegmentMap>> {
+  // Only use the table when `ttf_parser` accepted it as well.
+  face.tables().avar?;
+  let data = face.raw_face().table(Tag::from_bytes(b"avar"))?;
+  let mut s = Stream::new(data);
+  s.skip(6)?; // majorVersion, minorVersion, reserved
+  let axis_count = s.read_u16()?;
+
+  let mut maps = Vec::with_capacity(axis_count.into());
+  for _ in 0..axis_count {
+    let count = s.read_u16()?;
+    let map = (0..count)
+      .map(|_| Some((s.read_i16()?, s.read_i16()?)))
+      .collect::<Option<SegmentMap>>()?;
+    maps.push(map);
+  }
+
+  Some(maps)
+}
+
+/// Maps a normalized coordinate through a segment map.
+///
+/// Uses the same integer arithmetic as `ttf_parser` and HarfBuzz.
+fn map_value(map: &[(i16, i16)], value: i16) -> i16 {
+  let Some(&first) = map.first() else {
+    return value;
+  };
+
+  if map.len() == 1 || value <= first.0 {
+    return value.wrapping_sub(first.0).wrapping_add(first.1);
+  }
+
+  let i = map
+    .iter()
+    .position(|&(from, _)| value <= from)
+    .unwrap_or(map.len() - 1);
+
+  let (curr_from, curr_to) = map[i];
+  if value >= curr_from {
+    return value.wrapping_sub(curr_from).wrapping_add(curr_to);
+  }
+
+  let (prev_from, prev_to) = map[i - 1];
+  if prev_from == curr_from {
+    return prev_to;
+  }
+
+  // Maps spanning the whole `i16` range overflow `i32` products.
+  let (prev_from, prev_to) = (i64::from(prev_from), i64::from(prev_to));
+  let (curr_from, curr_to) = (i64::from(curr_from), i64::from(curr_to));
+  let denom = curr_from - prev_from;
+  let k = (curr_to - prev_to) * (i64::from(value) - prev_from) + denom / 2;
+  (prev_to + k / denom) as i16
+}
+
+/// Reverses [`map_value`] with linear interpolation, for a coordinate in the
+/// -1..1 range.
+fn unmap_value(map: &[(i16, i16)], value: f32) -> f32 {
+  let to_f32 = |v: i16| f32::from(v) / ONE as f32;
+  let (Some(&first), Some(&last)) = (map.first(), map.last()) else {
+    return value;
+  };
+
+  if map.len() == 1 || value <= to_f32(first.1) {
+    return value - to_f32(first.1) + to_f32(first.0);
+  }
+  if value >= to_f32(last.1) {
+    return value - to_f32(last.1) + to_f32(last.0);
+  }
+
+  for pair in map.windows(2) {
+    let (prev_from, prev_to) = (to_f32(pair[0].0), to_f32(pair[0].1));
+    let (curr_from, curr_to) = (to_f32(pair[1].0), to_f32(pair[1].1));
+    if value <= curr_to {
+      if curr_to == prev_to {
+        return prev_from;
+      }
+      return prev_from + (value - prev_to) * (curr_from - prev_from) / (curr_to - prev_to);
+    }
+  }
+
+  value
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const HALF: i16 = ONE as i16 / 2;
+
+  /// Maps -0.5 to -0.25 and 0.5 to 0.8, like a typical `wght` map.
+  fn weight_map() -> SegmentMap {
+    vec![
+      (-ONE as i16, -ONE as i16),
+      (-HALF, -HALF / 2),
+      (0, 0),
+      (HALF, 13107),
+      (ONE as i16, ONE as i16),
+    ]
+  }
+
+  fn axis(tag: &[u8; 4], range: (f32, f32, f32), segment_map: Option<SegmentMap>) -> Axis {
+    Axis {
+      tag: Tag::from_bytes(tag),
+      min_value: range.0,
+      def_value: range.1,
+      max_value: range.2,
+      segment_map,
+    }
+  }
+
+  fn axes() -> Axes {
+    Axes {
+      axes: vec![
+        axis(b"wght", (100.0, 400.0, 900.0), Some(weight_map())),
+        axis(
+          b"wdth",
+          (50.0, 100.0, 200.0),
+          Some(vec![(0, 0), (HALF, 4096)]),
+        ),
+        axis(b"opsz", (8.0, 12.0, 72.0), Some(Vec::new())),
+      ],
+    }
+  }
+
+  /// Sets coordinates the way `Face::set_variation` does: each call
+  /// normalizes its own axis, then maps every coordinate through `avar`.
+  fn set_variations(axes: &Axes, variations: &[(Tag, f32)]) -> Vec<i16> {
+    let mut coordinates = vec![0; axes.axes.len()];
+    axes.apply(variations, |tag, value| {
+      let index = axes.position(tag).unwrap();
+      coordinates[index] = axes.axes[index].normalize_default(value);
+      for (coordinate, axis) in coordinates.iter_mut().zip(&axes.axes) {
+        *coordinate = axis.map(*coordinate);
+      }
+    });
+    coordinates
+  }
+
+  #[test]
+  fn maps_values() {
+    let map = weight_map();
+    assert_eq!(map_value(&map, -ONE as i16), -ONE as i16);
+    assert_eq!(map_value(&map, -HALF), -HALF / 2);
+    assert_eq!(map_value(&map, 0), 0);
+    assert_eq!(map_value(&map, HALF / 2), 6554);
+    assert_eq!(map_value(&map, HALF), 13107);
+    assert_eq!(map_value(&map, ONE as i16), ONE as i16);
+
+    // Values past the ends are shifted by the first or last mapping.
+    assert_eq!(map_value(&[(0, 100)], -50), 50);
+    assert_eq!(map_value(&[(0, 0), (HALF, 4096)], ONE as i16), 12288);
+    assert_eq!(map_value(&[], 123), 123);
+  }
+
+  #[test]
+  fn maps_values_without_overflow() {
+    let map = [(i16::MIN, i16::MIN), (i16::MAX, i16::MAX)];
+    for value in [i16::MIN, -1, 0, 1, 12345, i16::MAX] {
+      assert_eq!(map_value(&map, value), value);
+    }
+
+    let map = [(i16::MIN, i16::MAX), (i16::MAX, i16::MIN)];
+    assert_eq!(map_value(&map, 1), -1);
+  }
+
+  #[test]
+  fn unmaps_values() {
+    let map = weight_map();
+    for value in [
+      -ONE as i16,
+      -12000,
+      -HALF,
+      -1000,
+      0,
+      2000,
+      HALF,
+      15000,
+      ONE as i16,
+    ] {
+      let mapped = f32::from(map_value(&map, value)) / ONE as f32;
+      let unmapped = unmap_value(&map, mapped) * ONE as f32;
+      assert!(
+        (unmapped - f32::from(value)).abs() <= 1.0,
+        "{value}: {unmapped}"
+      );
+    }
+
+    assert_eq!(unmap_value(&[(0, 100)], 0.0), -100.0 / ONE as f32);
+    assert_eq!(unmap_value(&[], 0.25), 0.25);
+  }
+
+  #[test]
+  fn finds_preimages() {
+    assert_eq!(preimage(1234, |c| c), 1234);
+    assert_eq!(preimage(-ONE as i16, |c| c), -ONE as i16);
+    assert_eq!(preimage(ONE as i16, |c| c), ONE as i16);
+
+    let map = weight_map();
+    let twice = |c| map_value(&map, map_value(&map, c));
+    for target in [-ONE as i16, -9000, 0, 777, 13107, ONE as i16] {
+      // Not every target is reachable, but none is closer than the preimage.
+      let coordinate = preimage(target, twice);
+      let distance = |c: i16| (twice(c) - target).abs();
+      assert!(distance(coordinate) <= distance(coordinate.saturating_sub(1)));
+      assert!(distance(coordinate) <= distance(coordinate.saturating_add(1)));
+    }
+
+    // Flat segments resolve to their first coordinate.
+    assert_eq!(preimage(0, |c| c.max(0)), -ONE as i16);
+    // Unreachable targets resolve to the closest coordinate.
+    assert_eq!(preimage(100, |c| c / 2 * 2), 100);
+    assert_eq!(preimage(101, |c| c / 4 * 4), 103);
+  }
+
+  #[test]
+  fn round_trips_normalized_coordinates() {
+    let axes = axes();
+    for (index, values) in [
+      (0, [100.0, 250.0, 399.0, 400.0, 550.0, 700.0, 900.0]),
+      (1, [50.0, 75.0, 99.0, 100.0, 120.0, 150.0, 200.0]),
+      (2, [8.0, 9.5, 11.0, 12.0, 14.0, 40.0, 72.0]),
+    ] {
+      for value in values {
+        let coordinate = axes.normalize(index, value);
+        let user = axes.denormalize(index, f32::from(coordinate) / ONE as f32);
+        assert!((user - value).abs() < 0.05, "{index} {value}: {user}");
+        let again = axes.normalize(index, user);
+        assert!((again - coordinate).abs() <= 1, "{index} {value}: {again}");
+      }
+    }
+
+    // Out of range values are clamped.
+    assert_eq!(axes.normalize(0, 1000.0), ONE as i16);
+    assert_eq!(axes.denormalize(0, 2.0), 900.0);
+  }
+
+  #[test]
+  fn applies_variations_in_any_order() {
+    let axes = axes();
+    let (wght, wdth, opsz) = (
+      Tag::from_bytes(b"wght"),
+      Tag::from_bytes(b"wdth"),
+      Tag::from_bytes(b"opsz"),
+    );
+    let expected = set_variations(&axes, &[(wght, 600.0), (wdth, 150.0)]);
+    let normalized = [axes.normalize(0, 600.0), axes.normalize(1, 150.0), 0];
+    for (coordinate, normalized) in expected.iter().zip(normalized) {
+      assert!((coordinate - normalized).abs() <= 1, "{expected:?}");
+    }
+
+    // What one `setVariations` call or successive `setVariation` calls store.
+    for variations in [
+      vec![(wdth, 150.0), (wght, 600.0)],
+      vec![(wdth, 150.0), (opsz, 12.0), (wght, 600.0)],
+      vec![(opsz, 12.0), (wght, 600.0), (wdth, 150.0)],
+    ] {
+      assert_eq!(set_variations(&axes, &variations), expected);
+    }
+
+    // The last axis of `fvar` is mapped once and always exact.
+    assert_eq!(
+      set_variations(&axes, &[(wght, 600.0), (opsz, 40.0)])[2],
+      axes.normalize(2, 40.0)
+    );
+
+    // Unknown axes are skipped.
+    let ital = Tag::from_bytes(b"ital");
+    assert_eq!(
+      set_variations(&axes, &[(wght, 600.0), (ital, 1.0), (wdth, 150.0)]),
+      expected
+    );
+  }
+}
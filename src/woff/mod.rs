@@ -0,0 +1,167 @@
+use std::io::Read;
+
+use crate::CustomError;
+
+mod woff1;
+mod woff2;
+
+/// The largest decoded font accepted, far above any real font.
+///
+/// Sizes are declared by the file, so they are checked against this limit
+/// before decompressing anything.
+const MAX_SFNT_SIZE: usize = 1 << 30;
+
+/// A font decoded from a [WOFF](https://www.w3.org/TR/WOFF/) or
+/// [WOFF2](https://www.w3.org/TR/WOFF2/) file.
+pub struct WebFont {
+  /// The reconstructed sfnt or font collection data.
+  pub sfnt: Vec<u8>,
+
+  /// The decompressed extended metadata block, an XML document.
+  pub metadata: Option<String>,
+
+  /// The private data block.
+  pub private_data: Option<Vec<u8>>,
+}
+
+/// Decodes `data` when it starts with a WOFF or WOFF2 signature.
+///
+/// Returns `Ok(None)` for any other data, so it can be passed on as is.
+pub fn decode(data: &[u8]) -> Result<Option<WebFont>, CustomError> {
+  let font = match data.get(0..4) {
+    Some(b"wOFF") => woff1::decode(data),
+    Some(b"wOF2") => woff2::decode(data),
+    _ => return Ok(None),
+  };
+
+  font.map(Some).ok_or(CustomError::MalformedWoff)
+}
+
+/// Checks the sum of the table sizes declared by a web font against its
+/// declared `totalSfntSize` and [`MAX_SFNT_SIZE`].
+fn check_sfnt_size(
+  table_sizes: impl IntoIterator<Item = usize>,
+  total_sfnt_size: u32,
+) -> Option<()> {
+  let size = table_sizes
+    .into_iter()
+    .try_fold(0usize, |sum, size| sum.checked_add(size))?;
+
+  (size <= total_sfnt_size as usize && size <= MAX_SFNT_SIZE).then_some(())
+}
+
+/// Reads all of `reader`, failing unless exactly `len` bytes are produced.
+///
+/// `len` comes from the file, so the buffer grows with the data actually
+/// read instead of being allocated upfront.
+fn read_exact_len(reader: impl Read, len: usize) -> Option<Vec<u8>> {
+  if len > MAX_SFNT_SIZE {
+    return None;
+  }
+
+  let mut out = Vec::new();
+  reader.take(len as u64 + 1).read_to_end(&mut out).ok()?;
+
+  if out.len() == len {
+    Some(out)
+  } else {
+    None
+  }
+}
+
+/// A table of the reconstructed sfnt.
+pub struct SfntTable {
+  pub tag: [u8; 4],
+  pub data: Vec<u8>,
+}
+
+/// A face of the reconstructed font, referencing tables by index.
+pub struct SfntFace {
+  pub flavor: u32,
+  pub tables: Vec<usize>,
+}
+
+fn checksum(data: &[u8]) -> u32 {
+  data.chunks(4).fold(0u32, |sum, chunk| {
+    let mut word = [0; 4];
+    word[..chunk.len()].copy_from_slice(chunk);
+    sum.wrapping_add(u32::from_be_bytes(word))
+  })
+}
+
+fn pad4(len: usize) -> usize {
+  (len + 3) & !3
+}
+
+/// Lays out a single font or, when `collection_version` is set, a font
+/// collection whose faces share table data.
+///
+/// Returns `None` when a face has too many tables for an sfnt header.
+pub fn write_sfnt(
+  collection_version: Option<u32>,
+  faces: &[SfntFace],
+  tables: &[SfntTable],
+) -> Option<Vec<u8>> {
+  let header_len = match collection_version {
+    Some(version) if version >= 0x0002_0000 => 24 + 4 * faces.len(),
+    Some(_) => 12 + 4 * faces.len(),
+    None => 0,
+  };
+
+  let mut directory_offsets = Vec::with_capacity(faces.len());
+  let mut offset = header_len;
+  for face in faces {
+    directory_offsets.push(offset);
+    offset += 12 + 16 * face.tables.len();
+  }
+
+  let mut table_offsets = Vec::with_capacity(tables.len());
+  for table in tables {
+    table_offsets.push(offset);
+    offset += pad4(table.data.len());
+  }
+
+  let mut out = Vec::with_capacity(offset);
+  if let Some(version) = collection_version {
+    out.extend_from_slice(b"ttcf");
+    out.extend_from_slice(&version.to_be_bytes());
+    out.extend_from_slice(&(faces.len() as u32).to_be_bytes());
+    for directory_offset in &directory_offsets {
+      out.extend_from_slice(&(*directory_offset as u32).to_be_bytes());
+    }
+    if version >= 0x0002_0000 {
+      // No DSIG table.
+      out.extend_from_slice(&[0; 12]);
+    }
+  }
+
+  for face in faces {
+    // The binary search fields only fit in 16 bits below 4096 tables.
+    let num_tables = face.tables.len() as u32;
+    // A face needs at least the `head`, `hhea` and `maxp` tables anyway.
+    let entry_selector = num_tables.checked_ilog2()?;
+    let search_range = (1 << entry_selector) * 16;
+    let range_shift = num_tables * 16 - search_range;
+    out.extend_from_slice(&face.flavor.to_be_bytes());
+    for value in [num_tables, search_range, entry_selector, range_shift] {
+      out.extend_from_slice(&u16::try_from(value).ok()?.to_be_bytes());
+    }
+
+    let mut records = face.tables.clone();
+    records.sort_by_key(|&index| tables[index].tag);
+    for index in records {
+      let table = &tables[index];
+      out.extend_from_slice(&table.tag);
+      out.extend_from_slice(&checksum(&table.data).to_be_bytes());
+      out.extend_from_slice(&(table_offsets[index] as u32).to_be_bytes());
+      out.extend_from_slice(&(table.data.len() as u32).to_be_bytes());
+    }
+  }
+
+  for table in tables {
+    out.extend_from_slice(&table.data);
+    out.resize(pad4(out.len()), 0);
+  }
+
+  Some(out)
+}
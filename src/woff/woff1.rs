@@ -0,0 +1,106 @@
+use flate2::read::ZlibDecoder;
+
+use super::{check_sfnt_size, read_exact_len, write_sfnt, SfntFace, SfntTable, WebFont};
+use crate::stream::Stream;
+
+fn inflate(data: &[u8], len: usize) -> Option<Vec<u8>> {
+  read_exact_len(ZlibDecoder::new(data), len)
+}
+
+fn block(data: &[u8], offset: u32, len: u32) -> Option<&[u8]> {
+  let start = offset as usize;
+  data.get(start..start.checked_add(len as usize)?)
+}
+
+/// Decodes a [WOFF 1.0](https://www.w3.org/TR/WOFF/) file.
+pub fn decode(data: &[u8]) -> Option<WebFont> {
+  let mut s = Stream::new(data);
+  s.skip(4)?; // signature
+  let flavor = s.read_u32()?;
+  s.skip(4)?; // length
+  let num_tables = s.read_u16()?;
+  s.skip(2)?; // reserved
+  let total_sfnt_size = s.read_u32()?;
+  s.skip(4)?; // majorVersion, minorVersion
+  let meta_offset = s.read_u32()?;
+  let meta_length = s.read_u32()?;
+  let meta_orig_length = s.read_u32()?;
+  let priv_offset = s.read_u32()?;
+  let priv_length = s.read_u32()?;
+
+  let mut entries = Vec::with_capacity(num_tables as usize);
+  for _ in 0..num_tables {
+    let tag: [u8; 4] = s.read_bytes(4)?.try_into().ok()?;
+    let offset = s.read_u32()?;
+    let comp_length = s.read_u32()?;
+    let orig_length = s.read_u32()?;
+    s.skip(4)?; // origChecksum
+    entries.push((tag, offset, comp_length, orig_length));
+  }
+
+  check_sfnt_size(
+    entries
+      .iter()
+      .map(|&(_, _, _, orig_length)| orig_length as usize),
+    total_sfnt_size,
+  )?;
+
+  let mut tables = Vec::with_capacity(entries.len());
+  for (tag, offset, comp_length, orig_length) in entries {
+    let compressed = block(data, offset, comp_length)?;
+    let data = if comp_length < orig_length {
+      inflate(compressed, orig_length as usize)?
+    } else if comp_length == orig_length {
+      compressed.to_vec()
+    } else {
+      return None;
+    };
+
+    tables.push(SfntTable { tag, data });
+  }
+
+  let metadata = if meta_length != 0 {
+    let xml = inflate(
+      block(data, meta_offset, meta_length)?,
+      meta_orig_length as usize,
+    )?;
+    Some(String::from_utf8(xml).ok()?)
+  } else {
+    None
+  };
+
+  let private_data = if priv_length != 0 {
+    Some(block(data, priv_offset, priv_length)?.to_vec())
+  } else {
+    None
+  };
+
+  let face = SfntFace {
+    flavor,
+    tables: (0..tables.len()).collect(),
+  };
+
+  Some(WebFont {
+    sfnt: write_sfnt(None, &[face], &tables)?,
+    metadata,
+    private_data,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rejects_fonts_without_tables() {
+    let mut data = b"wOFF".to_vec();
+    data.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+    data.extend_from_slice(&44u32.to_be_bytes()); // length
+    data.extend_from_slice(&[0; 4]); // numTables, reserved
+    data.extend_from_slice(&12u32.to_be_bytes()); // totalSfntSize
+    data.extend_from_slice(&[0; 24]); // versions, metadata and private data
+    assert_eq!(data.len(), 44);
+
+    assert!(decode(&data).is_none());
+  }
+}
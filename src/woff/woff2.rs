@@ -0,0 +1,784 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use brotli_decompressor::Decompressor;
+
+use super::{check_sfnt_size, read_exact_len, write_sfnt, SfntFace, SfntTable, WebFont};
+use crate::stream::Stream;
+
+/// Tags addressable by the 6-bit index of a table directory entry.
+const KNOWN_TAGS: [&[u8; 4]; 63] = [
+  b"cmap", b"head", b"hhea", b"hmtx", b"maxp", b"name", b"OS/2", b"post", b"cvt ", b"fpgm",
+  b"glyf", b"loca", b"prep", b"CFF ", b"VORG", b"EBDT", b"EBLC", b"gasp", b"hdmx", b"kern",
+  b"LTSH", b"PCLT", b"VDMX", b"vhea", b"vmtx", b"BASE", b"GDEF", b"GPOS", b"GSUB", b"EBSC",
+  b"JSTF", b"MATH", b"CBDT", b"CBLC", b"COLR", b"CPAL", b"SVG ", b"sbix", b"acnt", b"avar",
+  b"bdat", b"bloc", b"bsln", b"cvar", b"fdsc", b"feat", b"fmtx", b"fvar", b"gvar", b"hsty",
+  b"just", b"lcar", b"mort", b"morx", b"opbd", b"prop", b"trak", b"Zapf", b"Silf", b"Glat",
+  b"Gloc", b"Feat", b"Sill",
+];
+
+const TTC_FLAVOR: u32 = u32::from_be_bytes(*b"ttcf");
+
+// Composite glyph flags.
+const ARG_1_AND_2_ARE_WORDS: u16 = 0x0001;
+const WE_HAVE_A_SCALE: u16 = 0x0008;
+const MORE_COMPONENTS: u16 = 0x0020;
+const WE_HAVE_AN_X_AND_Y_SCALE: u16 = 0x0040;
+const WE_HAVE_A_TWO_BY_TWO: u16 = 0x0080;
+const WE_HAVE_INSTRUCTIONS: u16 = 0x0100;
+
+// Simple glyph flags.
+const ON_CURVE_POINT: u8 = 0x01;
+const X_SHORT_VECTOR: u8 = 0x02;
+const Y_SHORT_VECTOR: u8 = 0x04;
+const X_IS_SAME_OR_POSITIVE: u8 = 0x10;
+const Y_IS_SAME_OR_POSITIVE: u8 = 0x20;
+const OVERLAP_SIMPLE: u8 = 0x40;
+
+struct TableEntry {
+  tag: [u8; 4],
+  transformed: bool,
+  offset: usize,
+  length: usize,
+}
+
+/// A `glyf` table rebuilt from its transformed form, along with its `loca`.
+struct Glyf {
+  glyf: Vec<u8>,
+  loca: Vec<u8>,
+  /// Per-glyph `xMin`, needed to rebuild a transformed `hmtx`.
+  x_mins: Vec<i16>,
+}
+
+fn read_base128(s: &mut Stream) -> Option<usize> {
+  let mut value: u32 = 0;
+  for i in 0..5 {
+    let byte = s.read_u8()?;
+    // No leading zeros and no overflow.
+    if (i == 0 && byte == 0x80) || value & 0xFE00_0000 != 0 {
+      return None;
+    }
+
+    value = (value << 7) | u32::from(byte & 0x7F);
+    if byte & 0x80 == 0 {
+      return Some(value as usize);
+    }
+  }
+
+  None
+}
+
+fn read_255_u16(s: &mut Stream) -> Option<u16> {
+  const LOWEST_U_CODE: u16 = 253;
+
+  match s.read_u8()? {
+    253 => s.read_u16(),
+    254 => Some(LOWEST_U_CODE * 2 + u16::from(s.read_u8()?)),
+    255 => Some(LOWEST_U_CODE + u16::from(s.read_u8()?)),
+    code => Some(u16::from(code)),
+  }
+}
+
+fn brotli(data: &[u8], len: usize) -> Option<Vec<u8>> {
+  read_exact_len(Decompressor::new(data, 4096), len)
+}
+
+/// Decodes a [WOFF2](https://www.w3.org/TR/WOFF2/) file.
+pub fn decode(data: &[u8]) -> Option<WebFont> {
+  let mut s = Stream::new(data);
+  s.skip(4)?; // signature
+  let flavor = s.read_u32()?;
+  s.skip(4)?; // length
+  let num_tables = s.read_u16()?;
+  s.skip(2)?; // reserved
+  let total_sfnt_size = s.read_u32()?;
+  let total_compressed_size = s.read_u32()? as usize;
+  s.skip(4)?; // majorVersion, minorVersion
+  let meta_offset = s.read_u32()? as usize;
+  let meta_length = s.read_u32()? as usize;
+  let meta_orig_length = s.read_u32()? as usize;
+  let priv_offset = s.read_u32()? as usize;
+  let priv_length = s.read_u32()? as usize;
+
+  let mut entries = Vec::with_capacity(num_tables as usize);
+  let mut orig_lengths = Vec::with_capacity(num_tables as usize);
+  let mut offset = 0usize;
+  for _ in 0..num_tables {
+    let flags = s.read_u8()?;
+    let tag = match flags & 0x3F {
+      0x3F => s.read_bytes(4)?.try_into().ok()?,
+      index => *KNOWN_TAGS[index as usize],
+    };
+
+    // For `glyf` and `loca` version 0 is the transform and version 3 the
+    // null transform. `hmtx` has a transform as version 1 and other tables
+    // only have the null transform. Other versions are reserved.
+    let transformed = match (&tag, flags >> 6) {
+      (b"glyf" | b"loca", version) => match version {
+        0 => true,
+        3 => false,
+        _ => return None,
+      },
+      (b"hmtx", version) => match version {
+        0 => false,
+        1 => true,
+        _ => return None,
+      },
+      (_, 0) => false,
+      _ => return None,
+    };
+
+    let orig_length = read_base128(&mut s)?;
+    let length = if transformed {
+      read_base128(&mut s)?
+    } else {
+      orig_length
+    };
+
+    entries.push(TableEntry {
+      tag,
+      transformed,
+      offset,
+      length,
+    });
+    orig_lengths.push(orig_length);
+    offset = offset.checked_add(length)?;
+  }
+
+  // Transformed tables are usually smaller than their original, so the
+  // decompressed stream is checked as well.
+  check_sfnt_size(orig_lengths, total_sfnt_size)?;
+  if offset > super::MAX_SFNT_SIZE {
+    return None;
+  }
+
+  let (collection_version, faces) = if flavor == TTC_FLAVOR {
+    let version = s.read_u32()?;
+    let num_fonts = read_255_u16(&mut s)?;
+    let mut faces = Vec::with_capacity(num_fonts as usize);
+    for _ in 0..num_fonts {
+      let num_tables = read_255_u16(&mut s)?;
+      let flavor = s.read_u32()?;
+      let tables = (0..num_tables)
+        .map(|_| {
+          read_255_u16(&mut s)
+            .map(usize::from)
+            .filter(|&index| index < entries.len())
+        })
+        .collect::<Option<Vec<usize>>>()?;
+      faces.push(SfntFace { flavor, tables });
+    }
+
+    (Some(version), faces)
+  } else {
+    let face = SfntFace {
+      flavor,
+      tables: (0..entries.len()).collect(),
+    };
+
+    (None, vec![face])
+  };
+
+  let stream = brotli(s.read_bytes(total_compressed_size)?, offset)?;
+  let tables = reconstruct_tables(&entries, &faces, &stream)?;
+
+  let metadata = if meta_length != 0 {
+    let compressed = data.get(meta_offset..meta_offset.checked_add(meta_length)?)?;
+    Some(String::from_utf8(brotli(compressed, meta_orig_length)?).ok()?)
+  } else {
+    None
+  };
+
+  let private_data = if priv_length != 0 {
+    Some(
+      data
+        .get(priv_offset..priv_offset.checked_add(priv_length)?)?
+        .to_vec(),
+    )
+  } else {
+    None
+  };
+
+  Some(WebFont {
+    sfnt: write_sfnt(collection_version, &faces, &tables)?,
+    metadata,
+    private_data,
+  })
+}
+
+fn reconstruct_tables(
+  entries: &[TableEntry],
+  faces: &[SfntFace],
+  stream: &[u8],
+) -> Option<Vec<SfntTable>> {
+  let source = |index: usize| -> &[u8] {
+    let entry = &entries[index];
+    &stream[entry.offset..entry.offset + entry.length]
+  };
+  let find = |face: &SfntFace, tag: &[u8; 4]| {
+    face
+      .tables
+      .iter()
+      .copied()
+      .find(|&index| &entries[index].tag == tag)
+  };
+
+  let mut tables: Vec<Option<Vec<u8>>> = entries
+    .iter()
+    .enumerate()
+    .map(|(index, entry)| (!entry.transformed).then(|| source(index).to_vec()))
+    .collect();
+
+  // Faces of a collection may share a `glyf` table, but `hmtx` still needs
+  // the `xMin` values of the one it belongs to.
+  let mut x_mins: HashMap<usize, Vec<i16>> = HashMap::new();
+
+  for face in faces {
+    let glyf_index = find(face, b"glyf");
+    if let Some(glyf_index) = glyf_index.filter(|&index| entries[index].transformed) {
+      if let Entry::Vacant(entry) = x_mins.entry(glyf_index) {
+        let loca_index = find(face, b"loca")?;
+        let glyf = reconstruct_glyf(source(glyf_index))?;
+        tables[glyf_index] = Some(glyf.glyf);
+        tables[loca_index] = Some(glyf.loca);
+        entry.insert(glyf.x_mins);
+      }
+    }
+
+    if let Some(hmtx_index) = find(face, b"hmtx").filter(|&index| entries[index].transformed) {
+      let hhea = tables[find(face, b"hhea")?].as_deref()?;
+      let num_h_metrics = Stream::new_at(hhea, 34)?.read_u16()?;
+      let x_mins = x_mins.get(&glyf_index?)?;
+      tables[hmtx_index] = Some(reconstruct_hmtx(source(hmtx_index), num_h_metrics, x_mins)?);
+    }
+  }
+
+  entries
+    .iter()
+    .zip(tables)
+    .map(|(entry, data)| {
+      // Fails on a transformed table that wasn't rebuilt, e.g. a `loca`
+      // without a transformed `glyf`.
+      Some(SfntTable {
+        tag: entry.tag,
+        data: data?,
+      })
+    })
+    .collect()
+}
+
+fn reconstruct_glyf(data: &[u8]) -> Option<Glyf> {
+  let mut s = Stream::new(data);
+  s.skip(2)?; // reserved
+  let option_flags = s.read_u16()?;
+  let num_glyphs = s.read_u16()? as usize;
+  let index_format = s.read_u16()?;
+
+  let mut sizes = [0usize; 7];
+  for size in &mut sizes {
+    *size = s.read_u32()? as usize;
+  }
+  let [n_contour_size, n_points_size, flag_size, glyph_size, composite_size, bbox_size, instruction_size] =
+    sizes;
+
+  let mut n_contour_stream = Stream::new(s.read_bytes(n_contour_size)?);
+  let mut n_points_stream = Stream::new(s.read_bytes(n_points_size)?);
+  let mut flag_stream = Stream::new(s.read_bytes(flag_size)?);
+  let mut glyph_stream = Stream::new(s.read_bytes(glyph_size)?);
+  let mut composite_stream = Stream::new(s.read_bytes(composite_size)?);
+  let mut bbox_stream = Stream::new(s.read_bytes(bbox_size)?);
+  let mut instruction_stream = Stream::new(s.read_bytes(instruction_size)?);
+  let overlap_bitmap = if option_flags & 1 != 0 {
+    Some(s.read_bytes(num_glyphs.div_ceil(8))?)
+  } else {
+    None
+  };
+
+  let bbox_bitmap = bbox_stream.read_bytes(4 * num_glyphs.div_ceil(32))?;
+  let bit = |bitmap: &[u8], index: usize| bitmap[index >> 3] & (0x80 >> (index & 7)) != 0;
+
+  let mut glyf = Vec::new();
+  let mut offsets = Vec::with_capacity(num_glyphs + 1);
+  let mut x_mins = Vec::with_capacity(num_glyphs);
+
+  for index in 0..num_glyphs {
+    offsets.push(glyf.len());
+
+    let has_bbox = bit(bbox_bitmap, index);
+    let n_contours = n_contour_stream.read_i16()?;
+    match n_contours {
+      0 => {
+        // An empty glyph can't have a bounding box.
+        if has_bbox {
+          return None;
+        }
+        x_mins.push(0);
+      }
+      -1 => {
+        // Composite glyphs always have an explicit bounding box.
+        if !has_bbox {
+          return None;
+        }
+        let bbox = bbox_stream.read_bytes(8)?;
+        let (components, has_instructions) = read_composite(&mut composite_stream)?;
+
+        glyf.extend_from_slice(&n_contours.to_be_bytes());
+        glyf.extend_from_slice(bbox);
+        glyf.extend_from_slice(components);
+        if has_instructions {
+          let len = read_255_u16(&mut glyph_stream)?;
+          glyf.extend_from_slice(&len.to_be_bytes());
+          glyf.extend_from_slice(instruction_stream.read_bytes(len as usize)?);
+        }
+
+        x_mins.push(i16::from_be_bytes([bbox[0], bbox[1]]));
+      }
+      n if n > 0 => {
+        let mut end_points = Vec::with_capacity(n as usize);
+        let mut num_points = 0usize;
+        for _ in 0..n {
+          num_points += read_255_u16(&mut n_points_stream)? as usize;
+          end_points.push(u16::try_from(num_points.checked_sub(1)?).ok()?);
+        }
+
+        let mut points = Vec::with_capacity(num_points);
+        let (mut x, mut y) = (0i32, 0i32);
+        for _ in 0..num_points {
+          let flag = flag_stream.read_u8()?;
+          let (dx, dy) = read_triplet(flag & 0x7F, &mut glyph_stream)?;
+          x += dx;
+          y += dy;
+          points.push((
+            i16::try_from(x).ok()?,
+            i16::try_from(y).ok()?,
+            flag & 0x80 == 0,
+          ));
+        }
+
+        let instructions_len = read_255_u16(&mut glyph_stream)?;
+        let instructions = instruction_stream.read_bytes(instructions_len as usize)?;
+
+        let bbox = if has_bbox {
+          [
+            bbox_stream.read_i16()?,
+            bbox_stream.read_i16()?,
+            bbox_stream.read_i16()?,
+            bbox_stream.read_i16()?,
+          ]
+        } else {
+          points.iter().fold(
+            [i16::MAX, i16::MAX, i16::MIN, i16::MIN],
+            |[x_min, y_min, x_max, y_max], &(x, y, _)| {
+              [x_min.min(x), y_min.min(y), x_max.max(x), y_max.max(y)]
+            },
+          )
+        };
+
+        glyf.extend_from_slice(&n_contours.to_be_bytes());
+        for value in bbox {
+          glyf.extend_from_slice(&value.to_be_bytes());
+        }
+        for end_point in end_points {
+          glyf.extend_from_slice(&end_point.to_be_bytes());
+        }
+        glyf.extend_from_slice(&instructions_len.to_be_bytes());
+        glyf.extend_from_slice(instructions);
+        let overlap = overlap_bitmap.is_some_and(|bitmap| bit(bitmap, index));
+        write_points(&mut glyf, &points, overlap);
+
+        x_mins.push(bbox[0]);
+      }
+      _ => return None,
+    }
+
+    glyf.resize(super::pad4(glyf.len()), 0);
+  }
+  offsets.push(glyf.len());
+
+  let mut loca = Vec::with_capacity(offsets.len() * 4);
+  for offset in offsets {
+    if index_format == 0 {
+      loca.extend_from_slice(&u16::try_from(offset / 2).ok()?.to_be_bytes());
+    } else {
+      loca.extend_from_slice(&(offset as u32).to_be_bytes());
+    }
+  }
+
+  Some(Glyf { glyf, loca, x_mins })
+}
+
+/// Returns the raw component records of a composite glyph and whether it
+/// has instructions.
+fn read_composite<'a>(s: &mut Stream<'a>) -> Option<(&'a [u8], bool)> {
+  let mut peek = *s;
+  let mut has_instructions = false;
+  loop {
+    let flags = peek.read_u16()?;
+    has_instructions |= flags & WE_HAVE_INSTRUCTIONS != 0;
+
+    let args_len = if flags & ARG_1_AND_2_ARE_WORDS != 0 {
+      4
+    } else {
+      2
+    };
+    let transform_len = if flags & WE_HAVE_A_SCALE != 0 {
+      2
+    } else if flags & WE_HAVE_AN_X_AND_Y_SCALE != 0 {
+      4
+    } else if flags & WE_HAVE_A_TWO_BY_TWO != 0 {
+      8
+    } else {
+      0
+    };
+    // glyphIndex, arguments and transform.
+    peek.skip(2 + args_len + transform_len)?;
+
+    if flags & MORE_COMPONENTS == 0 {
+      break;
+    }
+  }
+
+  let len = peek.offset() - s.offset();
+  Some((s.read_bytes(len)?, has_instructions))
+}
+
+/// Decodes a point delta using the WOFF2 triplet encoding.
+fn read_triplet(flag: u8, s: &mut Stream) -> Option<(i32, i32)> {
+  fn with_sign(flag: u8, value: i32) -> i32 {
+    if flag & 1 != 0 {
+      value
+    } else {
+      -value
+    }
+  }
+
+  let flag_value = i32::from(flag);
+  let delta = if flag < 10 {
+    let b0 = i32::from(s.read_u8()?);
+    (0, with_sign(flag, ((flag_value & 14) << 7) + b0))
+  } else if flag < 20 {
+    let b0 = i32::from(s.read_u8()?);
+    (with_sign(flag, (((flag_value - 10) & 14) << 7) + b0), 0)
+  } else if flag < 84 {
+    let b0 = flag_value - 20;
+    let b1 = i32::from(s.read_u8()?);
+    (
+      with_sign(flag, 1 + (b0 & 0x30) + (b1 >> 4)),
+      with_sign(flag >> 1, 1 + ((b0 & 0x0C) << 2) + (b1 & 0x0F)),
+    )
+  } else if flag < 120 {
+    let b0 = flag_value - 84;
+    let b1 = i32::from(s.read_u8()?);
+    let b2 = i32::from(s.read_u8()?);
+    (
+      with_sign(flag, 1 + ((b0 / 12) << 8) + b1),
+      with_sign(flag >> 1, 1 + (((b0 % 12) >> 2) << 8) + b2),
+    )
+  } else if flag < 124 {
+    let b1 = i32::from(s.read_u8()?);
+    let b2 = i32::from(s.read_u8()?);
+    let b3 = i32::from(s.read_u8()?);
+    (
+      with_sign(flag, (b1 << 4) + (b2 >> 4)),
+      with_sign(flag >> 1, ((b2 & 0x0F) << 8) + b3),
+    )
+  } else {
+    let dx = i32::from(s.read_u16()?);
+    let dy = i32::from(s.read_u16()?);
+    (with_sign(flag, dx), with_sign(flag >> 1, dy))
+  };
+
+  Some(delta)
+}
+
+/// Writes the flags and coordinates of a simple glyph, without flag repeats.
+fn write_points(glyf: &mut Vec<u8>, points: &[(i16, i16, bool)], overlap: bool) {
+  let mut flags = Vec::with_capacity(points.len());
+  let mut xs = Vec::with_capacity(points.len() * 2);
+  let mut ys = Vec::with_capacity(points.len() * 2);
+
+  let (mut last_x, mut last_y) = (0i32, 0i32);
+  for (index, &(x, y, on_curve)) in points.iter().enumerate() {
+    let mut flag = if on_curve { ON_CURVE_POINT } else { 0 };
+    if overlap && index == 0 {
+      flag |= OVERLAP_SIMPLE;
+    }
+
+    let dx = i32::from(x) - last_x;
+    if dx == 0 {
+      flag |= X_IS_SAME_OR_POSITIVE;
+    } else if dx.abs() < 256 {
+      flag |= X_SHORT_VECTOR | if dx > 0 { X_IS_SAME_OR_POSITIVE } else { 0 };
+      xs.push(dx.unsigned_abs() as u8);
+    } else {
+      xs.extend_from_slice(&(dx as i16).to_be_bytes());
+    }
+
+    let dy = i32::from(y) - last_y;
+    if dy == 0 {
+      flag |= Y_IS_SAME_OR_POSITIVE;
+    } else if dy.abs() < 256 {
+      flag |= Y_SHORT_VECTOR | if dy > 0 { Y_IS_SAME_OR_POSITIVE } else { 0 };
+      ys.push(dy.unsigned_abs() as u8);
+    } else {
+      ys.extend_from_slice(&(dy as i16).to_be_bytes());
+    }
+
+    flags.push(flag);
+    last_x = i32::from(x);
+    last_y = i32::from(y);
+  }
+
+  glyf.extend_from_slice(&flags);
+  glyf.extend_from_slice(&xs);
+  glyf.extend_from_slice(&ys);
+}
+
+fn reconstruct_hmtx(data: &[u8], num_h_metrics: u16, x_mins: &[i16]) -> Option<Vec<u8>> {
+  let num_h_metrics = num_h_metrics as usize;
+  let num_glyphs = x_mins.len();
+  if num_h_metrics == 0 || num_h_metrics > num_glyphs {
+    return None;
+  }
+
+  let mut s = Stream::new(data);
+  let flags = s.read_u8()?;
+  let advances = (0..num_h_metrics)
+    .map(|_| s.read_u16())
+    .collect::<Option<Vec<u16>>>()?;
+
+  let mut side_bearings = Vec::with_capacity(num_glyphs);
+  for (index, &x_min) in x_mins.iter().enumerate() {
+    // Bit 0 drops proportional side bearings, bit 1 the monospaced ones.
+    let explicit = if index < num_h_metrics {
+      flags & 1 == 0
+    } else {
+      flags & 2 == 0
+    };
+    side_bearings.push(if explicit { s.read_i16()? } else { x_min });
+  }
+
+  let mut hmtx = Vec::with_capacity(num_h_metrics * 2 + num_glyphs * 2);
+  for (index, side_bearing) in side_bearings.into_iter().enumerate() {
+    if let Some(advance) = advances.get(index) {
+      hmtx.extend_from_slice(&advance.to_be_bytes());
+    }
+    hmtx.extend_from_slice(&side_bearing.to_be_bytes());
+  }
+
+  Some(hmtx)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::woff::write_sfnt;
+  use ttf_parser::{RawFace, Tag};
+
+  /// Wraps `data` in a Brotli stream made of a single uncompressed meta-block.
+  fn brotli_stored(data: &[u8]) -> Vec<u8> {
+    // WBITS = 16, ISLAST = 0, MNIBBLES = 4, MLEN - 1, ISUNCOMPRESSED = 1.
+    let header = ((data.len() as u32 - 1) << 4) | (1 << 20);
+    let mut out = header.to_le_bytes()[..3].to_vec();
+    out.extend_from_slice(data);
+    // ISLAST = 1, ISLASTEMPTY = 1.
+    out.push(0b11);
+    out
+  }
+
+  /// Builds a WOFF2 file from directory entries, without transforms, and the
+  /// concatenated table data.
+  fn woff2(directory: &[u8], num_tables: u16, total_sfnt_size: u32, tables: &[u8]) -> Vec<u8> {
+    let compressed = brotli_stored(tables);
+    let mut out = Vec::new();
+    out.extend_from_slice(b"wOF2");
+    out.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+    out.extend_from_slice(&0u32.to_be_bytes()); // length
+    out.extend_from_slice(&num_tables.to_be_bytes());
+    out.extend_from_slice(&0u16.to_be_bytes()); // reserved
+    out.extend_from_slice(&total_sfnt_size.to_be_bytes());
+    out.extend_from_slice(&(compressed.len() as u32).to_be_bytes());
+    out.extend_from_slice(&[0; 4]); // majorVersion, minorVersion
+    out.extend_from_slice(&[0; 20]); // metadata and private data
+    out.extend_from_slice(directory);
+    out.extend_from_slice(&compressed);
+    out
+  }
+
+  #[test]
+  fn reads_255_u16() {
+    let read = |data: &[u8]| read_255_u16(&mut Stream::new(data));
+    assert_eq!(read(&[5]), Some(5));
+    assert_eq!(read(&[252]), Some(252));
+    assert_eq!(read(&[255, 0]), Some(253));
+    assert_eq!(read(&[255, 5]), Some(258));
+    assert_eq!(read(&[254, 0]), Some(506));
+    assert_eq!(read(&[254, 255]), Some(761));
+    assert_eq!(read(&[253, 0x12, 0x34]), Some(0x1234));
+    assert_eq!(read(&[253, 0x12]), None);
+    assert_eq!(read(&[]), None);
+  }
+
+  #[test]
+  fn reads_triplets() {
+    let read = |flag: u8, data: &[u8]| read_triplet(flag, &mut Stream::new(data));
+    // Vertical deltas.
+    assert_eq!(read(0, &[5]), Some((0, -5)));
+    assert_eq!(read(1, &[5]), Some((0, 5)));
+    assert_eq!(read(3, &[5]), Some((0, 261)));
+    // Horizontal deltas.
+    assert_eq!(read(10, &[7]), Some((-7, 0)));
+    assert_eq!(read(11, &[7]), Some((7, 0)));
+    // Both deltas in one byte.
+    assert_eq!(read(20, &[0x12]), Some((-2, -3)));
+    assert_eq!(read(23, &[0x12]), Some((2, 3)));
+    // Both deltas in two bytes.
+    assert_eq!(read(84, &[1, 2]), Some((-2, -3)));
+    assert_eq!(read(99, &[1, 2]), Some((258, 3)));
+    // Both deltas in three bytes.
+    assert_eq!(read(120, &[0x12, 0x34, 0x56]), Some((-0x123, -0x456)));
+    assert_eq!(read(123, &[0x12, 0x34, 0x56]), Some((0x123, 0x456)));
+    // Both deltas in four bytes.
+    assert_eq!(
+      read(124, &[0x12, 0x34, 0x56, 0x78]),
+      Some((-0x1234, -0x5678))
+    );
+    assert_eq!(read(127, &[0x12, 0x34, 0x56, 0x78]), Some((0x1234, 0x5678)));
+
+    assert_eq!(read(120, &[0x12, 0x34]), None);
+  }
+
+  #[test]
+  fn reconstructs_hmtx() {
+    let x_mins = [10, 20, 30];
+    let words =
+      |values: &[i16]| -> Vec<u8> { values.iter().flat_map(|v| v.to_be_bytes()).collect() };
+    let transformed = |flags: u8, values: &[i16]| [vec![flags], words(values)].concat();
+
+    // Explicit side bearings.
+    assert_eq!(
+      reconstruct_hmtx(&transformed(0, &[500, 600, 1, 2, 3]), 2, &x_mins),
+      Some(words(&[500, 1, 600, 2, 3])),
+    );
+    // Side bearings taken from `xMin`.
+    assert_eq!(
+      reconstruct_hmtx(&transformed(3, &[500, 600]), 2, &x_mins),
+      Some(words(&[500, 10, 600, 20, 30])),
+    );
+    // Only proportional side bearings taken from `xMin`.
+    assert_eq!(
+      reconstruct_hmtx(&transformed(1, &[500, 600, -5]), 2, &x_mins),
+      Some(words(&[500, 10, 600, 20, -5])),
+    );
+
+    assert_eq!(reconstruct_hmtx(&transformed(3, &[500]), 0, &x_mins), None);
+    assert_eq!(
+      reconstruct_hmtx(&transformed(3, &[500; 4]), 4, &x_mins),
+      None
+    );
+    assert_eq!(
+      reconstruct_hmtx(&transformed(0, &[500, 600, 1]), 2, &x_mins),
+      None
+    );
+  }
+
+  #[test]
+  fn reconstructs_glyf() {
+    let streams: [&[u8]; 7] = [
+      // nContours of an empty glyph and a single contour.
+      &[0, 0, 0, 1],
+      // nPoints.
+      &[3],
+      // Flags: (0, 0) and (+100, 0) on-curve, (-50, +100) off-curve.
+      &[1, 11, 0x80 | 86],
+      // Deltas, then the instruction length.
+      &[0, 100, 49, 99, 0],
+      // Composites.
+      &[],
+      // Bounding box bitmap, all computed.
+      &[0; 4],
+      // Instructions.
+      &[],
+    ];
+    let mut data = vec![0, 0, 0, 0, 0, 2, 0, 0];
+    for stream in streams {
+      data.extend_from_slice(&(stream.len() as u32).to_be_bytes());
+    }
+    data.extend(streams.concat());
+
+    let glyf = reconstruct_glyf(&data).unwrap();
+    #[rustfmt::skip]
+    let expected = [
+      0, 1, // numberOfContours
+      0, 0, 0, 0, 0, 100, 0, 100, // xMin, yMin, xMax, yMax
+      0, 2, // endPtsOfContours
+      0, 0, // instructionLength
+      0x31, 0x33, 0x26, // flags
+      100, 50, // xCoordinates
+      100, // yCoordinates
+    ];
+    assert_eq!(glyf.glyf, expected);
+    assert_eq!(glyf.loca, [0, 0, 0, 0, 0, 10]);
+    assert_eq!(glyf.x_mins, [0, 0]);
+  }
+
+  #[test]
+  fn decodes_tables() {
+    // `head` by its known tag index, then an arbitrary tag.
+    let directory = [&[0x01, 4][..], &[0x3F], b"TEST", &[3]].concat();
+    let data = decode(&woff2(&directory, 2, 64, b"headTST")).unwrap();
+
+    let face = RawFace::parse(&data.sfnt, 0).unwrap();
+    assert_eq!(face.table(Tag::from_bytes(b"head")), Some(&b"head"[..]));
+    assert_eq!(face.table(Tag::from_bytes(b"TEST")), Some(&b"TST"[..]));
+    // numTables, searchRange, entrySelector and rangeShift.
+    assert_eq!(data.sfnt[4..12], [0, 2, 0, 32, 0, 1, 0, 0]);
+    assert!(data.metadata.is_none());
+    assert!(data.private_data.is_none());
+  }
+
+  #[test]
+  fn rejects_reserved_transforms() {
+    // `head` with transform version 1.
+    let directory = [0x41, 4, 4];
+    assert!(decode(&woff2(&directory, 1, 64, b"head")).is_none());
+    // `glyf` with transform version 1.
+    let directory = [0x4A, 4, 4];
+    assert!(decode(&woff2(&directory, 1, 64, b"glyf")).is_none());
+  }
+
+  #[test]
+  fn rejects_oversized_tables() {
+    // An `origLength` larger than `totalSfntSize`.
+    let directory = [0x01, 4];
+    assert!(decode(&woff2(&directory, 1, 2, b"head")).is_none());
+
+    // An `origLength` of `0xFFFFFFFF`, repeated.
+    let directory = [0x01, 0x8F, 0xFF, 0xFF, 0xFF, 0x7F].repeat(2000);
+    assert!(decode(&woff2(&directory, 2000, 12_000, b"head")).is_none());
+  }
+
+  #[test]
+  fn rejects_too_many_tables_per_face() {
+    let tables = [SfntTable {
+      tag: *b"head",
+      data: Vec::new(),
+    }];
+    let face = |count| SfntFace {
+      flavor: 0x0001_0000,
+      tables: vec![0; count],
+    };
+
+    assert!(write_sfnt(None, &[face(4095)], &tables).is_some());
+    assert!(write_sfnt(None, &[face(4096)], &tables).is_none());
+  }
+
+  #[test]
+  fn rejects_faces_without_tables() {
+    let face = SfntFace {
+      flavor: 0x0001_0000,
+      tables: Vec::new(),
+    };
+    assert!(write_sfnt(None, &[face], &[]).is_none());
+    assert!(decode(&woff2(&[], 0, 12, &[0])).is_none());
+  }
+}